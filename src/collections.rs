@@ -1,4 +1,6 @@
+use std::cmp::{max, min};
 use std::collections::HashSet;
+use std::ops::{Add, Range, Sub};
 
 // split off an arbitrary element from a (non-empty) set
 pub fn hashset_pop<T>(set: &mut HashSet<T>) -> Option<T>
@@ -6,12 +8,208 @@ where
     T: Eq + Copy + Clone + std::hash::Hash,
 {
     let elt = set.iter().next().cloned();
-    if elt.is_some() {
-        set.remove(&elt.unwrap());
+    if let Some(e) = elt {
+        set.remove(&e);
     }
     elt
 }
 
+// a set of values stored as sorted, disjoint, non-touching half open ranges,
+// so that huge ranges cost no more than small ones
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // the ranges making up the set, in order
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // everything overlapping or touching the new range merges into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = min(merged.start, self.ranges[first].start);
+            merged.end = max(merged.end, self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        let mut kept = vec![];
+        for r in &self.ranges[first..last] {
+            if r.start < range.start {
+                kept.push(r.start..range.start);
+            }
+            if range.end < r.end {
+                kept.push(range.end..r.end);
+            }
+        }
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        i < self.ranges.len() && self.ranges[i].start <= value
+    }
+
+    // whether every value of range is in the set
+    pub fn contains_range(&self, range: Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        i < self.ranges.len()
+            && self.ranges[i].start <= range.start
+            && range.end <= self.ranges[i].end
+    }
+
+    // the values in either set
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut set = self.clone();
+        for r in &other.ranges {
+            set.insert(r.clone());
+        }
+
+        set
+    }
+
+    // the values in both sets
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let r = max(a.start, b.start)..min(a.end, b.end);
+            if !r.is_empty() {
+                ranges.push(r);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    // total number of values in the set
+    pub fn total_length(&self) -> T
+    where
+        T: Default + Add<Output = T> + Sub<Output = T>,
+    {
+        self.ranges
+            .iter()
+            .fold(T::default(), |total, r| total + (r.end - r.start))
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        for r in iter {
+            set.insert(r);
+        }
+
+        set
+    }
+}
+
+// a piecewise shift, values in a source range move along with it to the
+// destination, anything not covered by a source range maps to itself
+#[derive(Clone, Debug, Default)]
+pub struct RangeMap<T> {
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> RangeMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> RangeMap<T> {
+        RangeMap { pieces: vec![] }
+    }
+
+    // map source onto the range of the same length starting at destination,
+    // source ranges must not overlap
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        let i = self.pieces.partition_point(|(r, _)| r.start < source.start);
+        self.pieces.insert(i, (source, destination));
+    }
+
+    pub fn map(&self, value: T) -> T {
+        match self.pieces.iter().find(|(r, _)| r.contains(&value)) {
+            Some((r, dest)) => value - r.start + *dest,
+            None => value,
+        }
+    }
+
+    // every value of the set mapped, splitting ranges wherever they cross the
+    // edge of a source range
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut mapped = IntervalSet::new();
+        for range in set.ranges() {
+            let mut start = range.start;
+            for (source, dest) in &self.pieces {
+                if start >= range.end {
+                    break;
+                }
+                if source.end <= start {
+                    continue;
+                }
+
+                // the stretch before this source range is left as is
+                if start < source.start {
+                    let end = min(range.end, source.start);
+                    mapped.insert(start..end);
+                    start = end;
+                }
+                if start < range.end && start < source.end {
+                    let end = min(range.end, source.end);
+                    mapped.insert(start - source.start + *dest..end - source.start + *dest);
+                    start = end;
+                }
+            }
+            if start < range.end {
+                mapped.insert(start..range.end);
+            }
+        }
+
+        mapped
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,4 +226,66 @@ mod tests {
 
         assert_eq!(None, hashset_pop(&mut s));
     }
+
+    #[test]
+    fn test_interval_insert() {
+        let mut set: IntervalSet<i64> = [10..20, 30..40].into_iter().collect();
+        assert_eq!(20, set.total_length());
+
+        set.insert(20..25);
+        assert_eq!(&[10..25, 30..40], set.ranges());
+        set.insert(5..35);
+        assert_eq!(Some(&(5..40)), set.ranges().first());
+        assert_eq!(1, set.ranges().len());
+        set.insert(50..50);
+        assert_eq!(35, set.total_length());
+    }
+
+    #[test]
+    fn test_interval_remove() {
+        let mut set: IntervalSet<i64> = [0..10, 20..30].into_iter().collect();
+        set.remove(5..25);
+        assert_eq!(&[0..5, 25..30], set.ranges());
+        set.remove(1..2);
+        assert_eq!(&[0..1, 2..5, 25..30], set.ranges());
+
+        assert!(set.contains(0));
+        assert!(!set.contains(1));
+        assert!(!set.contains(5));
+        assert!(set.contains_range(2..5));
+        assert!(!set.contains_range(2..6));
+
+        set.remove(-100..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_interval_union_intersection() {
+        let a: IntervalSet<i64> = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet<i64> = [5..25, 28..40].into_iter().collect();
+
+        assert_eq!(vec![0..40], a.union(&b).ranges().to_vec());
+        assert_eq!(&[5..10, 20..25, 28..30], a.intersection(&b).ranges());
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_range_map() {
+        // seed to soil: 50 98 2 and 52 50 48
+        let mut map = RangeMap::new();
+        map.insert(98..100, 50);
+        map.insert(50..98, 52);
+        assert_eq!(81, map.map(79));
+        assert_eq!(14, map.map(14));
+        assert_eq!(51, map.map(99));
+
+        let seeds: IntervalSet<i64> = [79..93, 55..68].into_iter().collect();
+        assert_eq!(&[57..70, 81..95], map.map_set(&seeds).ranges());
+
+        // 98..100 lands on 50..52, merging with what 45..55 became
+        let split: IntervalSet<i64> = [45..55, 95..105].into_iter().collect();
+        let mapped = map.map_set(&split);
+        assert_eq!(&[45..57, 97..105], mapped.ranges());
+        assert_eq!(20, mapped.total_length());
+    }
 }
@@ -35,10 +35,10 @@ fn get_grid(input: i64, max_x: i64, max_y: i64) -> Grid<char> {
     grid
 }
 
-fn build_graph<'a>(grid: Grid<char>) -> HashMap<(i64, i64), Vec<(i64, i64)>> {
+fn build_graph(grid: Grid<char>) -> HashMap<(i64, i64), Vec<(i64, i64)>> {
     let mut graph: HashMap<(i64, i64), Vec<(i64, i64)>> = HashMap::new();
-    for y in 0..grid.max_y() {
-        for x in 0..grid.max_x() {
+    for y in 0..=grid.max_y() {
+        for x in 0..=grid.max_x() {
             for o in OFFSETS_STRAIGHT.iter() {
                 let n = (x + o.0, y + o.1);
                 if let Some(val) = grid.get(n.0, n.1) {
@@ -46,9 +46,7 @@ fn build_graph<'a>(grid: Grid<char>) -> HashMap<(i64, i64), Vec<(i64, i64)>> {
                         if let Some(neighours) = graph.get_mut(&(x, y)) {
                             neighours.push(n);
                         } else {
-                            let mut neighbours = vec![];
-                            neighbours.push(n);
-                            graph.insert((x, y), neighbours);
+                            graph.insert((x, y), vec![n]);
                         }
                     }
                 }
@@ -104,12 +102,12 @@ fn bfs<'a>(
 
     let (_graph, _dist, prev) = dijkstra(start, start, graph);
 
-    while q.len() > 0 {
+    while !q.is_empty() {
         let cur = q.pop_front().unwrap();
         if let Some(neighbours) = graph.get(cur) {
             for n in neighbours {
                 if !visited.contains(n) {
-                    let path = get_path(&n, &prev);
+                    let path = get_path(n, &prev);
                     if path.len() - 1 <= max {
                         q.push_back(n);
                         visited.insert(n);
@@ -164,8 +162,8 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        // too costly in debug
-        // assert_eq!(127, part2(1364, 100));
-        assert_eq!(40, part2(1364, 10));
+        // 50 steps from (1, 1) can't get past 51, so a 52 wide grid is enough
+        assert_eq!(127, part2(1364, 52));
+        assert_eq!(43, part2(1364, 10));
     }
 }
@@ -28,7 +28,7 @@ fn part2(filename: &str) -> i64 {
 
     let mut nodes: HashSet<&i64> = graph.keys().collect();
     let mut groups = HashSet::new();
-    while nodes.len() > 0 {
+    while !nodes.is_empty() {
         let n = hashset_pop(&mut nodes).unwrap();
         let visited: HashSet<&i64> = bfs(n, &graph);
 
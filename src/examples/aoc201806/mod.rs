@@ -28,10 +28,10 @@ fn find_closest(x: i64, y: i64, grid: &mut Grid<i64>) -> Option<i64> {
         panic!("position not found");
     }
 
-    return grid.get(min_pos.unwrap().0, min_pos.unwrap().1);
+    grid.get(min_pos.unwrap().0, min_pos.unwrap().1)
 }
 
-fn parse_grid(lines: &Vec<String>) -> Grid<i64> {
+fn parse_grid(lines: &[String]) -> Grid<i64> {
     let mut grid = Grid::empty();
 
     for (i, line) in lines.iter().enumerate() {
@@ -53,8 +53,8 @@ fn solve1(filename: &str) -> i64 {
     let mut excluded_areas = HashSet::new();
     let mut areas: HashMap<i64, i64> = HashMap::new();
 
-    for x in 0..grid.max_x() + 1 {
-        for y in 0..grid.max_y() + 1 {
+    for x in 0..=grid.max_x() {
+        for y in 0..=grid.max_y() {
             if let Some(closest) = find_closest(x, y, &mut grid) {
                 areas.insert(closest, *areas.get(&closest).unwrap_or(&0) + 1);
                 if x == 0 || x == grid.max_x() || y == 0 || y == grid.max_y() {
@@ -66,10 +66,8 @@ fn solve1(filename: &str) -> i64 {
 
     let mut max_size = 0;
     for (area, count) in areas.iter() {
-        if !excluded_areas.contains(area) {
-            if max_size < *count {
-                max_size = *count;
-            }
+        if !excluded_areas.contains(area) && max_size < *count {
+            max_size = *count;
         }
     }
 
@@ -82,8 +80,8 @@ fn solve2(filename: &str, size: i64) -> i64 {
 
     let mut area_size = 0;
 
-    for x in 0..grid.max_x() + 1 {
-        for y in 0..grid.max_y() + 1 {
+    for x in 0..=grid.max_x() {
+        for y in 0..=grid.max_y() {
             let mut area_sum = 0;
             grid.walk(|pos, _v| area_sum += manhattan_dist(pos.0, pos.1, x, y));
             if area_sum < size {
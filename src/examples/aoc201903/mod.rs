@@ -1,80 +1,52 @@
 use std::cmp::min;
 
-use super::super::grid::Grid;
+use super::super::geometry::{segment_intersection, Intersection};
+use super::super::grid::{line, Dir4, Point};
 use super::super::io::lines_from_file;
 
-fn coords(x: i64, y: i64, direction: &str, length: i64) -> Vec<(i64, i64)> {
-    let mut r = vec![];
-    match direction {
-        "R" => {
-            for nx in x + 1..x + length + 1 {
-                r.push((nx, y));
-            }
-        }
-        "L" => {
-            for nx in x - length..x {
-                r.push((nx, y));
-            }
-            r.reverse();
-        }
-        "D" => {
-            for ny in y - length..y {
-                r.push((x, ny));
-            }
-            r.reverse();
-        }
-        "U" => {
-            for ny in y + 1..y + length + 1 {
-                r.push((x, ny));
-            }
-        }
-        _ => panic!("should not happen"),
+// the straight pieces of a wire with the number of steps taken before each,
+// laid out with U as y + 1
+fn segments(line: &str) -> Vec<(Point, Point, i64)> {
+    let mut pos = Point::new(0, 0);
+    let mut steps = 0;
+    let mut segments = vec![];
+
+    for mov in line.split(',') {
+        let dir: Dir4 = mov[0..1].parse().unwrap();
+        let length = mov[1..].parse::<i64>().unwrap();
+        let end = pos + dir.offset_y_up() * length;
+        segments.push((pos, end, steps));
+        pos = end;
+        steps += length;
     }
 
-    r
-}
-
-fn distance(coord: (i64, i64)) -> u64 {
-    (coord.0.abs() + coord.1.abs()) as u64
+    segments
 }
 
 fn solve(filename: &str) -> (u64, u64) {
     let contents = lines_from_file(filename);
-    let mut grid = Grid::<(u64, u64)>::empty();
+    let wire1 = segments(&contents[0]);
+    let wire2 = segments(&contents[1]);
+    let origin = Point::new(0, 0);
     let mut min_distance = u64::MAX;
     let mut min_steps = u64::MAX;
 
-    for (i, line) in contents.iter().enumerate() {
-        let mut x = 0;
-        let mut y = 0;
-        let mut cur_steps = 0;
-
-        for mov in line.split(',') {
-            let direction: &str = &mov[0..1];
-            let length = &mov[1..].parse::<i64>().unwrap();
-            let coords = coords(x, y, direction, *length);
-
-            for coord in coords {
-                if coord == (0, 0) {
-                    continue;
-                }
-
-                cur_steps += 1;
-
-                if let Some(value) = grid.get(coord.0, coord.1) {
-                    if value.0 != i as u64 && value.0 < 2 {
-                        grid.set((2, cur_steps + value.1), coord.0, coord.1);
-
-                        let new_distance = distance(coord);
-                        min_distance = min(min_distance, new_distance);
-                        min_steps = min(min_steps, cur_steps + value.1);
-                    }
-                } else {
-                    grid.set((i as u64, cur_steps), coord.0, coord.1);
+    for (a1, a2, steps1) in wire1.iter() {
+        for (b1, b2, steps2) in wire2.iter() {
+            let crossings: Vec<Point> = match segment_intersection(*a1, *a2, *b1, *b2) {
+                None => vec![],
+                Some(Intersection::Point(x, y)) => {
+                    let x = x.to_integer().unwrap() as i64;
+                    let y = y.to_integer().unwrap() as i64;
+                    vec![Point::new(x, y)]
                 }
+                Some(Intersection::Overlap(start, end)) => line(start, end).collect(),
+            };
 
-                x = coord.0;
-                y = coord.1;
+            for p in crossings.into_iter().filter(|p| *p != origin) {
+                let steps = steps1 + a1.manhattan(p) + steps2 + b1.manhattan(p);
+                min_distance = min(min_distance, p.manhattan(origin) as u64);
+                min_steps = min(min_steps, steps as u64);
             }
         }
     }
@@ -2,22 +2,7 @@ use super::super::grid::Grid;
 use super::super::io::lines_from_file;
 
 fn get_grid(filename: &str) -> Grid<char> {
-    let contents = lines_from_file(filename);
-
-    let height = contents.len();
-    let input_width = contents[0].len();
-    let extensions = ((height * 7) / input_width) + 1;
-    let mut grid = Grid::empty();
-
-    for (y, line) in contents.iter().enumerate() {
-        for extension in 0..extensions {
-            for (x, chr) in line.chars().enumerate() {
-                grid.set(chr, x as i64 + (extension * input_width) as i64, y as i64);
-            }
-        }
-    }
-
-    grid
+    Grid::new(&lines_from_file(filename))
 }
 
 #[allow(dead_code)]
@@ -31,11 +16,11 @@ fn count_trees(grid: &Grid<char>, down: i64, right: i64) -> u64 {
     let mut x = 0;
     let mut y = 0;
     let mut result = 0;
+    let slope = grid.wrapping(true, false);
 
-    while y < grid.max_y() + 1 {
-        match grid.get(x, y) {
-            Some('#') => result += 1,
-            _ => (),
+    while y <= grid.max_y() {
+        if let Some('#') = slope.get(x, y) {
+            result += 1
         }
 
         x += right;
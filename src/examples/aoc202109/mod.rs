@@ -1,19 +1,16 @@
-use std::collections::HashSet;
-
-use super::super::graph::bfs;
-use super::super::grid::{Grid, OFFSETS};
+use super::super::grid::{Grid, Point};
 use super::super::io::lines_from_file;
 
 fn find_lowest(grid: &Grid<char>) -> Vec<(i64, i64)> {
     let mut res = vec![];
 
-    for y in 0..grid.max_y() {
-        for x in 0..grid.max_x() {
+    for y in grid.min_y()..=grid.max_y() {
+        for x in grid.min_x()..=grid.max_x() {
             let mut is_lowest = true;
             let value = grid.get(x, y).unwrap_or('9');
 
-            for o in OFFSETS.iter() {
-                if let Some(neighbour) = grid.get(x + o.0, y + o.1) {
+            for n in Point::new(x, y).neighbours8() {
+                if let Some(neighbour) = grid.get_point(n) {
                     if neighbour <= value {
                         is_lowest = false;
                         break;
@@ -32,7 +29,7 @@ fn find_lowest(grid: &Grid<char>) -> Vec<(i64, i64)> {
 
 #[allow(dead_code)]
 fn solve1(filename: &str) -> u64 {
-    let grid: Grid<char> = Grid::<char>::new(&lines_from_file(filename));
+    let grid: Grid<char> = Grid::new(&lines_from_file(filename));
 
     let mut risk = 0;
     for pos in find_lowest(&grid) {
@@ -44,14 +41,14 @@ fn solve1(filename: &str) -> u64 {
 
 #[allow(dead_code)]
 fn solve2(filename: &str) -> i64 {
-    let grid: Grid<char> = Grid::<char>::new(&lines_from_file(filename));
-    let values = HashSet::from_iter((0..9 as u8).map(|v| (v + 48) as char));
-
-    let graph = grid.to_graph(values);
-    let mut sizes = vec![];
-    for pos in find_lowest(&grid) {
-        sizes.push(bfs(&pos, &graph).len() as i64);
-    }
+    let grid: Grid<char> = Grid::new(&lines_from_file(filename));
+
+    // basins are bounded by 9s and each flows down to a single low point
+    let mut sizes: Vec<i64> = grid
+        .regions_where(|c| c != '9')
+        .iter()
+        .map(|r| r.area() as i64)
+        .collect();
 
     sizes.sort_by(|n1, n2| n2.cmp(n1));
 
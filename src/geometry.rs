@@ -0,0 +1,356 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+
+use crate::grid::{Point, Point3};
+use crate::math::gcd;
+
+// an exact fraction, always stored reduced with a positive denominator so that
+// equal values compare equal
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "denominator must not be 0");
+
+        let d = gcd(num, den) * den.signum();
+        Rational {
+            num: num / d,
+            den: den / d,
+        }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        if self.den == 1 {
+            Some(self.num)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Rational {
+        Rational::new(n as i128, 1)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+fn cross(a: Point, b: Point) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+fn dot(a: Point, b: Point) -> i128 {
+    a.x as i128 * b.x as i128 + a.y as i128 * b.y as i128
+}
+
+// whether p lies on the segment from a to b, ends included
+pub fn on_segment(p: Point, a: Point, b: Point) -> bool {
+    cross(b - a, p - a) == 0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Intersection {
+    // a single crossing, which only lies on the grid when both coordinates are
+    // whole
+    Point(Rational, Rational),
+    // collinear segments sharing more than one point, from the end nearest
+    // the first segment's start
+    Overlap(Point, Point),
+}
+
+// where the segments a1-a2 and b1-b2 (ends included) meet, if at all
+pub fn segment_intersection(a1: Point, a2: Point, b1: Point, b2: Point) -> Option<Intersection> {
+    let r = a2 - a1;
+    let s = b2 - b1;
+    let d = cross(r, s);
+
+    // the common case of one horizontal and one vertical segment
+    if d != 0 && (r.x == 0 || r.y == 0) && (s.x == 0 || s.y == 0) {
+        let (v, h) = if r.x == 0 { (a1, b1) } else { (b1, a1) };
+        let p = Point::new(v.x, h.y);
+        return if on_segment(p, a1, a2) && on_segment(p, b1, b2) {
+            Some(Intersection::Point(p.x.into(), p.y.into()))
+        } else {
+            None
+        };
+    }
+
+    if d == 0 {
+        return collinear_overlap(a1, a2, b1, b2);
+    }
+
+    // a1 + r * t == b1 + s * u with t and u both within [0, 1]
+    let w = b1 - a1;
+    let (t, u) = (cross(w, s), cross(w, r));
+    let within = |n: i128| {
+        if d > 0 {
+            n >= 0 && n <= d
+        } else {
+            n <= 0 && n >= d
+        }
+    };
+    if !within(t) || !within(u) {
+        return None;
+    }
+
+    Some(Intersection::Point(
+        Rational::new(a1.x as i128 * d + r.x as i128 * t, d),
+        Rational::new(a1.y as i128 * d + r.y as i128 * t, d),
+    ))
+}
+
+fn collinear_overlap(a1: Point, a2: Point, b1: Point, b2: Point) -> Option<Intersection> {
+    let point = |p: Point| Some(Intersection::Point(p.x.into(), p.y.into()));
+
+    // segments that are really single points
+    if a1 == a2 {
+        return if on_segment(a1, b1, b2) {
+            point(a1)
+        } else {
+            None
+        };
+    }
+    if b1 == b2 {
+        return if on_segment(b1, a1, a2) {
+            point(b1)
+        } else {
+            None
+        };
+    }
+
+    let r = a2 - a1;
+    if cross(r, b1 - a1) != 0 {
+        return None;
+    }
+
+    // everything is on one line now, so order the ends along it
+    let key = |p: Point| dot(p - a1, r);
+    let (b_lo, b_hi) = if key(b1) <= key(b2) {
+        (b1, b2)
+    } else {
+        (b2, b1)
+    };
+    let start = if key(b_lo) > 0 { b_lo } else { a1 };
+    let end = if key(b_hi) < key(a2) { b_hi } else { a2 };
+
+    match key(start).cmp(&key(end)) {
+        Ordering::Less => Some(Intersection::Overlap(start, end)),
+        Ordering::Equal => point(start),
+        Ordering::Greater => None,
+    }
+}
+
+// the times (t, u) at which p1 + d1 * t and p2 + d2 * u reach the point where
+// the lines cross, None for parallel lines
+pub fn line_intersection(
+    p1: Point,
+    d1: Point,
+    p2: Point,
+    d2: Point,
+) -> Option<(Rational, Rational)> {
+    let d = cross(d1, d2);
+    if d == 0 {
+        return None;
+    }
+
+    let w = p2 - p1;
+    Some((
+        Rational::new(cross(w, d2), d),
+        Rational::new(cross(w, d1), d),
+    ))
+}
+
+fn cross3(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot3(a: [i128; 3], b: [i128; 3]) -> i128 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+// like line_intersection, in 3D where lines that aren't parallel usually miss
+// each other as well
+pub fn line_intersection_3d(
+    p1: Point3,
+    d1: Point3,
+    p2: Point3,
+    d2: Point3,
+) -> Option<(Rational, Rational)> {
+    let wide = |p: Point3| p.map(|c| c as i128);
+    let (d1, d2) = (wide(d1), wide(d2));
+    let w = [0, 1, 2].map(|i| p2[i] as i128 - p1[i] as i128);
+
+    let n = cross3(d1, d2);
+    let nn = dot3(n, n);
+    if nn == 0 || dot3(w, n) != 0 {
+        return None;
+    }
+
+    Some((
+        Rational::new(dot3(cross3(w, d2), n), nn),
+        Rational::new(dot3(cross3(w, d1), n), nn),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i64, y: i64) -> Point {
+        Point::new(x, y)
+    }
+
+    fn at(x: i64, y: i64) -> Option<Intersection> {
+        Some(Intersection::Point(x.into(), y.into()))
+    }
+
+    #[test]
+    fn test_rational() {
+        let r = Rational::new(6, -4);
+        assert_eq!(-3, r.numer());
+        assert_eq!(2, r.denom());
+        assert_eq!(None, r.to_integer());
+        assert_eq!(Some(5), Rational::new(10, 2).to_integer());
+        assert!(Rational::new(1, 3) < Rational::new(1, 2));
+        assert_eq!("-3/2", r.to_string());
+        assert_eq!(-1.5, r.to_f64());
+    }
+
+    #[test]
+    fn test_on_segment() {
+        assert!(on_segment(p(2, 2), p(0, 0), p(4, 4)));
+        assert!(on_segment(p(4, 4), p(0, 0), p(4, 4)));
+        assert!(!on_segment(p(5, 5), p(0, 0), p(4, 4)));
+        assert!(!on_segment(p(2, 1), p(0, 0), p(4, 4)));
+    }
+
+    #[test]
+    fn test_axis_aligned() {
+        // the crossing wires example
+        assert_eq!(
+            at(3, 3),
+            segment_intersection(p(2, 3), p(6, 3), p(3, 5), p(3, 2))
+        );
+        assert_eq!(
+            None,
+            segment_intersection(p(2, 3), p(6, 3), p(7, 5), p(7, 2))
+        );
+        assert_eq!(
+            Some(Intersection::Overlap(p(3, 0), p(5, 0))),
+            segment_intersection(p(0, 0), p(5, 0), p(8, 0), p(3, 0))
+        );
+        assert_eq!(
+            at(5, 0),
+            segment_intersection(p(0, 0), p(5, 0), p(5, 0), p(9, 0))
+        );
+        assert_eq!(
+            None,
+            segment_intersection(p(0, 0), p(5, 0), p(6, 0), p(9, 0))
+        );
+        assert_eq!(
+            None,
+            segment_intersection(p(0, 0), p(5, 0), p(0, 1), p(5, 1))
+        );
+    }
+
+    #[test]
+    fn test_general() {
+        assert_eq!(
+            Some(Intersection::Point(
+                Rational::new(1, 2),
+                Rational::new(1, 2)
+            )),
+            segment_intersection(p(0, 0), p(1, 1), p(1, 0), p(0, 1))
+        );
+        assert_eq!(
+            at(2, 2),
+            segment_intersection(p(0, 0), p(4, 4), p(0, 4), p(4, 0))
+        );
+        assert_eq!(
+            None,
+            segment_intersection(p(0, 0), p(1, 1), p(3, 0), p(2, 1))
+        );
+        assert_eq!(
+            Some(Intersection::Overlap(p(1, 1), p(2, 2))),
+            segment_intersection(p(0, 0), p(2, 2), p(3, 3), p(1, 1))
+        );
+        assert_eq!(
+            at(1, 1),
+            segment_intersection(p(1, 1), p(1, 1), p(0, 0), p(3, 3))
+        );
+    }
+
+    #[test]
+    fn test_line_intersection() {
+        // hailstones A and B cross at (14.333, 15.333)
+        let (t, u) = line_intersection(p(19, 13), p(-2, 1), p(18, 19), p(-1, -1)).unwrap();
+        assert_eq!(Rational::new(7, 3), t);
+        assert_eq!(Rational::new(11, 3), u);
+        assert_eq!(
+            None,
+            line_intersection(p(18, 19), p(-1, -1), p(20, 25), p(-2, -2))
+        );
+    }
+
+    #[test]
+    fn test_line_intersection_3d() {
+        let (t, u) = line_intersection_3d([0, 0, 0], [1, 1, 1], [4, 0, 2], [-1, 1, 0]).unwrap();
+        assert_eq!(Rational::from(2), t);
+        assert_eq!(Rational::from(2), u);
+
+        // skew and parallel lines never meet
+        assert_eq!(
+            None,
+            line_intersection_3d([0, 0, 0], [1, 0, 0], [0, 1, 1], [0, 1, 0])
+        );
+        assert_eq!(
+            None,
+            line_intersection_3d([0, 0, 0], [1, 1, 1], [1, 0, 0], [2, 2, 2])
+        );
+    }
+}
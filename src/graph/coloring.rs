@@ -0,0 +1,274 @@
+use std::cmp::min;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// view the adjacency lists as an undirected graph, adding reverse edges and
+// nodes that only ever appear as neighbours
+fn undirected<'a, T>(graph: &'a HashMap<T, Vec<T>>) -> HashMap<&'a T, HashSet<&'a T>>
+where
+    T: Eq + Hash,
+{
+    let mut adjacency: HashMap<&'a T, HashSet<&'a T>> = HashMap::new();
+    for (node, neighbours) in graph {
+        adjacency.entry(node).or_default();
+        for n in neighbours {
+            adjacency.entry(node).or_default().insert(n);
+            adjacency.entry(n).or_default().insert(node);
+        }
+    }
+
+    adjacency
+}
+
+// split the graph into two sides with no edge inside either side, or return an
+// odd cycle proving that no such split exists
+pub fn bipartition<'a, T>(
+    graph: &'a HashMap<T, Vec<T>>,
+) -> Result<(HashSet<&'a T>, HashSet<&'a T>), Vec<&'a T>>
+where
+    T: Eq + Hash,
+{
+    let adjacency = undirected(graph);
+    let mut side: HashMap<&'a T, bool> = HashMap::new();
+    let mut parent: HashMap<&'a T, &'a T> = HashMap::new();
+    let mut depth: HashMap<&'a T, usize> = HashMap::new();
+
+    for &root in adjacency.keys() {
+        if side.contains_key(root) {
+            continue;
+        }
+
+        side.insert(root, false);
+        depth.insert(root, 0);
+        let mut q = VecDeque::new();
+        q.push_back(root);
+
+        while let Some(cur) = q.pop_front() {
+            for &n in &adjacency[cur] {
+                match side.get(n) {
+                    None => {
+                        side.insert(n, !side[cur]);
+                        depth.insert(n, depth[cur] + 1);
+                        parent.insert(n, cur);
+                        q.push_back(n);
+                    }
+                    Some(s) if *s == side[cur] => {
+                        return Err(odd_cycle(cur, n, &parent, &depth));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let mut left = HashSet::new();
+    let mut right = HashSet::new();
+    for (node, s) in side {
+        if s {
+            right.insert(node);
+        } else {
+            left.insert(node);
+        }
+    }
+
+    Ok((left, right))
+}
+
+// walk both ends of a conflicting edge up the bfs tree until they meet
+fn odd_cycle<'a, T>(
+    a: &'a T,
+    b: &'a T,
+    parent: &HashMap<&'a T, &'a T>,
+    depth: &HashMap<&'a T, usize>,
+) -> Vec<&'a T>
+where
+    T: Eq + Hash,
+{
+    let (mut a, mut b) = (a, b);
+    let mut a_path = vec![a];
+    let mut b_path = vec![b];
+
+    while depth[a] > depth[b] {
+        a = parent[a];
+        a_path.push(a);
+    }
+    while depth[b] > depth[a] {
+        b = parent[b];
+        b_path.push(b);
+    }
+    while a != b {
+        a = parent[a];
+        a_path.push(a);
+        b = parent[b];
+        b_path.push(b);
+    }
+
+    // the common ancestor is already the last element of a_path
+    b_path.pop();
+    b_path.reverse();
+    a_path.extend(b_path);
+
+    a_path
+}
+
+// assign one of k colors (0..k) to every node so that no neighbours share a
+// color, using backtracking with DSATUR ordering
+// https://en.wikipedia.org/wiki/DSatur
+pub fn color_graph<T>(graph: &HashMap<T, Vec<T>>, k: usize) -> Option<HashMap<&T, usize>>
+where
+    T: Eq + Hash,
+{
+    let adjacency = undirected(graph);
+    if adjacency
+        .iter()
+        .any(|(node, neighbours)| neighbours.contains(node))
+    {
+        return None;
+    }
+
+    let mut colors = HashMap::new();
+    if color_next(&adjacency, k, &mut colors) {
+        Some(colors)
+    } else {
+        None
+    }
+}
+
+fn color_next<'a, T>(
+    adjacency: &HashMap<&'a T, HashSet<&'a T>>,
+    k: usize,
+    colors: &mut HashMap<&'a T, usize>,
+) -> bool
+where
+    T: Eq + Hash,
+{
+    // pick the uncolored node with the most distinct neighbour colors, breaking
+    // ties by the number of uncolored neighbours
+    let next = adjacency
+        .iter()
+        .filter(|(node, _)| !colors.contains_key(*node))
+        .max_by_key(|(_, neighbours)| {
+            let saturation = neighbours
+                .iter()
+                .filter_map(|n| colors.get(n))
+                .collect::<HashSet<_>>()
+                .len();
+            let degree = neighbours
+                .iter()
+                .filter(|n| !colors.contains_key(*n))
+                .count();
+            (saturation, degree)
+        });
+
+    let (&node, neighbours) = match next {
+        Some(n) => n,
+        None => return true,
+    };
+
+    let used: HashSet<usize> = neighbours
+        .iter()
+        .filter_map(|n| colors.get(n).copied())
+        .collect();
+
+    // colors are interchangeable, so only ever open one new color at a time
+    let limit = min(k, colors.values().max().map_or(0, |c| c + 1) + 1);
+    for c in 0..limit {
+        if used.contains(&c) {
+            continue;
+        }
+
+        colors.insert(node, c);
+        if color_next(adjacency, k, colors) {
+            return true;
+        }
+        colors.remove(node);
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(len: i64) -> HashMap<i64, Vec<i64>> {
+        (0..len)
+            .map(|i| (i, vec![(i + 1) % len, (i + len - 1) % len]))
+            .collect()
+    }
+
+    #[test]
+    fn test_bipartition_empty() {
+        let graph: HashMap<i64, Vec<i64>> = HashMap::new();
+        let (left, right) = bipartition(&graph).unwrap();
+        assert_eq!(0, left.len());
+        assert_eq!(0, right.len());
+    }
+
+    #[test]
+    fn test_bipartition() {
+        let mut graph = cycle(6);
+        graph.insert(10, vec![11]);
+
+        let (left, right) = bipartition(&graph).unwrap();
+        assert_eq!(8, left.len() + right.len());
+        for (node, neighbours) in graph.iter() {
+            for n in neighbours {
+                assert_ne!(left.contains(node), left.contains(n));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bipartition_odd_cycle() {
+        let mut graph = cycle(7);
+        graph.insert(7, vec![0]);
+
+        let witness = bipartition(&graph).unwrap_err();
+        assert_eq!(7, witness.len());
+        for i in 0..witness.len() {
+            let a = witness[i];
+            let b = witness[(i + 1) % witness.len()];
+            assert!(graph[a].contains(b));
+        }
+    }
+
+    #[test]
+    fn test_bipartition_self_loop() {
+        let mut graph = HashMap::new();
+        graph.insert(1, vec![1]);
+
+        assert_eq!(vec![&1], bipartition(&graph).unwrap_err());
+    }
+
+    #[test]
+    fn test_color_graph() {
+        // an odd wheel needs four colors
+        let mut graph = cycle(5);
+        graph.insert(5, (0..5).collect());
+
+        assert_eq!(None, color_graph(&graph, 3));
+
+        let colors = color_graph(&graph, 4).unwrap();
+        assert_eq!(6, colors.len());
+        for (node, neighbours) in graph.iter() {
+            for n in neighbours {
+                assert_ne!(colors[node], colors[n]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_color_graph_directed_edges() {
+        let mut graph = HashMap::new();
+        graph.insert("a", vec!["b", "c"]);
+        graph.insert("b", vec!["c"]);
+
+        assert_eq!(None, color_graph(&graph, 2));
+        assert_eq!(3, color_graph(&graph, 3).unwrap().len());
+        assert_eq!(
+            Some(HashMap::new()),
+            color_graph(&HashMap::<i64, Vec<i64>>::new(), 0)
+        );
+    }
+}
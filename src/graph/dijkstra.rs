@@ -19,7 +19,7 @@ where
     }
     dist.insert(start, 0.0);
 
-    while q.len() > 0 {
+    while !q.is_empty() {
         if let Some(u) = get_min_dist(&q, &dist) {
             q.remove(u);
 
@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+// nodes reachable from start in reverse postorder of a dfs
+fn reverse_postorder<'a, T>(start: &'a T, graph: &'a HashMap<T, Vec<T>>) -> Vec<&'a T>
+where
+    T: Eq + Hash,
+{
+    let mut visited: HashSet<&'a T> = HashSet::new();
+    let mut order = vec![];
+    let mut stack: Vec<(&'a T, usize)> = vec![(start, 0)];
+    visited.insert(start);
+
+    while let Some((node, i)) = stack.pop() {
+        let neighbours = graph.get(node).map_or(&[][..], |n| n.as_slice());
+        if i < neighbours.len() {
+            stack.push((node, i + 1));
+            let n = &neighbours[i];
+            if visited.insert(n) {
+                stack.push((n, 0));
+            }
+        } else {
+            order.push(node);
+        }
+    }
+
+    order.reverse();
+    order
+}
+
+// immediate dominator of every node reachable from start in a directed graph,
+// the start node itself has none and is left out
+// https://www.cs.rice.edu/~keith/EMBED/dom.pdf
+pub fn immediate_dominators<'a, T>(
+    start: &'a T,
+    graph: &'a HashMap<T, Vec<T>>,
+) -> HashMap<&'a T, &'a T>
+where
+    T: Eq + Hash,
+{
+    let order = reverse_postorder(start, graph);
+    let index: HashMap<&'a T, usize> = order.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+    let mut preds: Vec<Vec<usize>> = vec![vec![]; order.len()];
+    for (i, node) in order.iter().enumerate() {
+        if let Some(neighbours) = graph.get(*node) {
+            for n in neighbours {
+                preds[index[n]].push(i);
+            }
+        }
+    }
+
+    let mut idom: Vec<Option<usize>> = vec![None; order.len()];
+    idom[0] = Some(0);
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for b in 1..order.len() {
+            let mut new_idom = None;
+            for &p in &preds[b] {
+                if idom[p].is_none() {
+                    continue;
+                }
+                new_idom = match new_idom {
+                    None => Some(p),
+                    Some(cur) => Some(intersect(&idom, p, cur)),
+                };
+            }
+
+            if new_idom != idom[b] {
+                idom[b] = new_idom;
+                changed = true;
+            }
+        }
+    }
+
+    idom.iter()
+        .enumerate()
+        .skip(1)
+        .filter_map(|(i, d)| d.map(|d| (order[i], order[d])))
+        .collect()
+}
+
+// walk two fingers up the dominator tree until they meet, nodes are indexed
+// in reverse postorder so a larger index is always further from the root
+fn intersect(idom: &[Option<usize>], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while a > b {
+            a = idom[a].unwrap();
+        }
+        while b > a {
+            b = idom[b].unwrap();
+        }
+    }
+
+    a
+}
+
+// nodes that every path from start to end must pass through, ordered from start
+// to end and including both, or empty if end can't be reached
+pub fn must_pass<'a, T>(start: &'a T, end: &'a T, graph: &'a HashMap<T, Vec<T>>) -> Vec<&'a T>
+where
+    T: Eq + Hash,
+{
+    let idom = immediate_dominators(start, graph);
+
+    let mut path = vec![];
+    if idom.contains_key(end) || end == start {
+        let mut u = end;
+        loop {
+            path.push(u);
+            if let Some(next) = idom.get(u) {
+                u = next;
+            } else {
+                break;
+            }
+        }
+        path.reverse();
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_graph() -> HashMap<i64, Vec<i64>> {
+        let mut graph = HashMap::new();
+        graph.insert(1, vec![2]);
+        graph.insert(2, vec![3, 4, 6]);
+        graph.insert(3, vec![5]);
+        graph.insert(4, vec![5]);
+        graph.insert(5, vec![2]);
+        graph.insert(6, vec![7]);
+        graph.insert(7, vec![8, 9]);
+        graph.insert(8, vec![10]);
+        graph.insert(9, vec![10]);
+        graph.insert(11, vec![10]);
+
+        graph
+    }
+
+    #[test]
+    fn test_immediate_dominators() {
+        let graph = get_graph();
+        let idom = immediate_dominators(&1, &graph);
+
+        assert_eq!(9, idom.len());
+        assert_eq!(None, idom.get(&1));
+        assert_eq!(Some(&&1), idom.get(&2));
+        assert_eq!(Some(&&2), idom.get(&3));
+        assert_eq!(Some(&&2), idom.get(&5));
+        assert_eq!(Some(&&7), idom.get(&10));
+        assert_eq!(None, idom.get(&11));
+    }
+
+    #[test]
+    fn test_immediate_dominators_empty() {
+        let graph = HashMap::new();
+        assert_eq!(0, immediate_dominators(&1, &graph).len());
+    }
+
+    #[test]
+    fn test_must_pass() {
+        let graph = get_graph();
+
+        assert_eq!(vec![&1, &2, &6, &7, &10], must_pass(&1, &10, &graph));
+        assert_eq!(vec![&1, &2, &5], must_pass(&1, &5, &graph));
+        assert_eq!(vec![&1], must_pass(&1, &1, &graph));
+        assert_eq!(0, must_pass(&1, &11, &graph).len());
+    }
+}
@@ -1,4 +1,9 @@
+pub mod coloring;
 pub mod dijkstra;
+pub mod dominator;
+
+pub use coloring::{bipartition, color_graph};
+pub use dominator::{immediate_dominators, must_pass};
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
@@ -26,7 +31,7 @@ where
     visited.insert(start);
     visitor(start);
 
-    while q.len() > 0 {
+    while !q.is_empty() {
         let cur = q.pop_front().unwrap();
         if let Some(neighbours) = graph.get(cur) {
             for n in neighbours {
@@ -65,7 +70,7 @@ where
     visited.insert(start);
     visitor(start);
 
-    while q.len() > 0 {
+    while !q.is_empty() {
         let cur = q.pop_back().unwrap();
         if !visited.contains(cur) {
             visited.insert(cur);
@@ -0,0 +1,342 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::hex::{hex_cube_add, HEX_OFFSETS};
+use super::Grid;
+
+pub use super::nd::{moore, von_neumann};
+
+// how many neighbours of a cell hold each value
+pub struct Counts<T> {
+    counts: Vec<(T, usize)>,
+}
+
+impl<T> Counts<T>
+where
+    T: Copy + PartialEq,
+{
+    pub fn new() -> Counts<T> {
+        Counts { counts: vec![] }
+    }
+
+    pub fn add(&mut self, value: T) {
+        match self.counts.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, count)) => *count += 1,
+            None => self.counts.push((value, 1)),
+        }
+    }
+
+    pub fn get(&self, value: T) -> usize {
+        self.counts
+            .iter()
+            .find(|(v, _)| *v == value)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl<T> Default for Counts<T>
+where
+    T: Copy + PartialEq,
+{
+    fn default() -> Counts<T> {
+        Counts::new()
+    }
+}
+
+// apply rule to every cell at once, given how the cells at the offsets (e.g.
+// OFFSETS or OFFSETS_STRAIGHT) around it are filled, cells outside the grid
+// are not counted
+pub fn step<T, F>(grid: &Grid<T>, offsets: &[(i64, i64)], rule: F) -> Grid<T>
+where
+    T: Copy + PartialEq,
+    F: Fn(T, &Counts<T>) -> T,
+{
+    step_with(
+        grid,
+        |grid, pos| {
+            let mut counts = Counts::new();
+            for o in offsets {
+                if let Some(v) = grid.get(pos.0 + o.0, pos.1 + o.1) {
+                    counts.add(v);
+                }
+            }
+            counts
+        },
+        rule,
+    )
+}
+
+// like step, with the neighbour counting left to the caller, for
+// neighbourhoods that aren't a fixed set of offsets
+pub fn step_with<T, N, F>(grid: &Grid<T>, neighbours: N, rule: F) -> Grid<T>
+where
+    T: Copy + PartialEq,
+    N: Fn(&Grid<T>, (i64, i64)) -> Counts<T>,
+    F: Fn(T, &Counts<T>) -> T,
+{
+    let mut new_grid = Grid::empty();
+    for (pos, value) in grid.data.iter() {
+        new_grid.set(rule(*value, &neighbours(grid, *pos)), pos.0, pos.1);
+    }
+
+    new_grid
+}
+
+// step until nothing changes, returning the final grid and the number of
+// steps that changed something
+pub fn run_until_stable<T, S>(grid: Grid<T>, mut step: S) -> (Grid<T>, usize)
+where
+    T: PartialEq,
+    S: FnMut(&Grid<T>) -> Grid<T>,
+{
+    let mut grid = grid;
+    let mut steps = 0;
+    loop {
+        let new_grid = step(&grid);
+        if new_grid == grid {
+            return (grid, steps);
+        }
+        grid = new_grid;
+        steps += 1;
+    }
+}
+
+pub fn run<S, F>(state: S, steps: usize, mut step: F) -> S
+where
+    F: FnMut(&S) -> S,
+{
+    let mut state = state;
+    for _ in 0..steps {
+        state = step(&state);
+    }
+
+    state
+}
+
+// a sequence of states that eventually repeats, states[start..] repeats with
+// period length
+pub struct Cycle<S> {
+    pub start: usize,
+    pub length: usize,
+    pub states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    // the state after n steps, however large n is
+    pub fn state_at(&self, n: usize) -> &S {
+        if n < self.start {
+            &self.states[n]
+        } else {
+            &self.states[self.start + (n - self.start) % self.length]
+        }
+    }
+}
+
+// step until a state repeats, states are compared through key so that e.g. a
+// grid can be keyed by grid_key
+pub fn find_cycle<S, K, F, G>(state: S, mut step: F, key: G) -> Cycle<S>
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = vec![];
+    let mut state = state;
+    loop {
+        if let Some(start) = seen.insert(key(&state), states.len()) {
+            return Cycle {
+                start,
+                length: states.len() - start,
+                states,
+            };
+        }
+
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+// a hashable snapshot of the grid's cells
+pub fn grid_key<T>(grid: &Grid<T>) -> Vec<((i64, i64), T)>
+where
+    T: Copy,
+{
+    let mut cells: Vec<((i64, i64), T)> = grid.data.iter().map(|(k, v)| (*k, *v)).collect();
+    cells.sort_by_key(|(pos, _)| *pos);
+    cells
+}
+
+// Sparse automata only keep track of the active cells, so they may grow without
+// bounds and work with any kind of position.
+
+// activate cells according to rule, given whether they are active and how many
+// of their neighbours are
+pub fn step_sparse<P, N, I, F>(active: &HashSet<P>, neighbours: N, rule: F) -> HashSet<P>
+where
+    P: Copy + Eq + Hash,
+    N: Fn(P) -> I,
+    I: IntoIterator<Item = P>,
+    F: Fn(bool, usize) -> bool,
+{
+    let mut counts: HashMap<P, usize> = HashMap::new();
+    for p in active {
+        for n in neighbours(*p) {
+            *counts.entry(n).or_insert(0) += 1;
+        }
+    }
+
+    let mut new_active: HashSet<P> = counts
+        .iter()
+        .filter(|(p, count)| rule(active.contains(*p), **count))
+        .map(|(p, _)| *p)
+        .collect();
+
+    for p in active {
+        if !counts.contains_key(p) && rule(true, 0) {
+            new_active.insert(*p);
+        }
+    }
+
+    new_active
+}
+
+// the 6 surrounding hexes in cube coordinates
+pub fn hex(p: (i64, i64, i64)) -> impl Iterator<Item = (i64, i64, i64)> {
+    HEX_OFFSETS.values().map(move |o| hex_cube_add(p, *o))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{OFFSETS, OFFSETS_STRAIGHT};
+
+    fn life(cell: char, counts: &Counts<char>) -> char {
+        match (cell, counts.get('#')) {
+            ('#', 2) | (_, 3) => '#',
+            _ => '.',
+        }
+    }
+
+    fn blinker() -> Grid<char> {
+        Grid::new(&[
+            ".....".to_string(),
+            "..#..".to_string(),
+            "..#..".to_string(),
+            "..#..".to_string(),
+            ".....".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_counts() {
+        let mut counts = Counts::new();
+        counts.add('a');
+        counts.add('b');
+        counts.add('a');
+        assert_eq!(2, counts.get('a'));
+        assert_eq!(1, counts.get('b'));
+        assert_eq!(0, counts.get('c'));
+        assert_eq!(3, counts.total());
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = blinker();
+        let next = step(&grid, &OFFSETS, life);
+        assert_eq!(
+            vec![".....", ".....", ".###.", ".....", "....."],
+            next.prepare_print()
+        );
+        assert_eq!(grid, step(&next, &OFFSETS, life));
+    }
+
+    #[test]
+    fn test_step_straight() {
+        let grid = blinker();
+        let next = step(&grid, &OFFSETS_STRAIGHT, |c, counts| {
+            if counts.get('#') > 0 {
+                '#'
+            } else {
+                c
+            }
+        });
+        assert_eq!(
+            vec!["..#..", ".###.", ".###.", ".###.", "..#.."],
+            next.prepare_print()
+        );
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let grid = Grid::new(&["#...".to_string()]);
+        let (grid, steps) = run_until_stable(grid, |g| {
+            step(g, &OFFSETS_STRAIGHT, |c, counts| {
+                if counts.get('#') > 0 {
+                    '#'
+                } else {
+                    c
+                }
+            })
+        });
+        assert_eq!(3, steps);
+        assert_eq!(vec!["####"], grid.prepare_print());
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = find_cycle(blinker(), |g| step(g, &OFFSETS, life), grid_key);
+        assert_eq!(0, cycle.start);
+        assert_eq!(2, cycle.length);
+        assert_eq!(blinker(), *cycle.state_at(1_000_000_000));
+
+        let cycle = find_cycle(0, |n| (n + 1) % 7 + 10, |n| *n);
+        assert_eq!(1, cycle.start);
+        assert_eq!(7, cycle.length);
+        assert_eq!(11, *cycle.state_at(1 + 7 * 1000));
+        assert_eq!(0, *cycle.state_at(0));
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(blinker(), run(blinker(), 10, |g| step(g, &OFFSETS, life)));
+    }
+
+    #[test]
+    fn test_hex_neighbourhood() {
+        assert_eq!(6, hex((0, 0, 0)).collect::<HashSet<_>>().len());
+    }
+
+    #[test]
+    fn test_step_sparse() {
+        // conway cubes example, 112 active cubes after 6 cycles
+        let mut active: HashSet<[i64; 3]> = HashSet::new();
+        for (y, line) in [".#.", "..#", "###"].iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    active.insert([x as i64, y as i64, 0]);
+                }
+            }
+        }
+
+        let rule = |on: bool, n: usize| n == 3 || (on && n == 2);
+        let active = run(active, 6, |a| step_sparse(a, moore, rule));
+        assert_eq!(112, active.len());
+    }
+
+    #[test]
+    fn test_step_sparse_hex() {
+        // a single tile with no neighbours survives only if rule(true, 0)
+        let mut active = HashSet::new();
+        active.insert((0, 0, 0));
+
+        assert_eq!(0, step_sparse(&active, hex, |on, n| on && n > 0).len());
+        assert_eq!(1, step_sparse(&active, hex, |on, _n| on).len());
+        assert_eq!(6, step_sparse(&active, hex, |on, n| !on && n == 1).len());
+    }
+}
@@ -0,0 +1,201 @@
+use super::{Grid, Point, Rect};
+
+// Maps huge sparse coordinates onto a small grid. Every interesting x and y
+// starts its own compressed column or row of width 1, and the stretches in
+// between are squashed into a single cell that remembers how wide it was.
+// There is always a border of cells around the points, so flood fills can go
+// all the way round.
+pub struct Compression {
+    // the real coordinate each column or row starts at, with one extra end
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+}
+
+pub fn compress<I, P>(points: I) -> Compression
+where
+    I: IntoIterator<Item = P>,
+    P: Into<Point>,
+{
+    let mut xs = vec![];
+    let mut ys = vec![];
+    for p in points {
+        let p = p.into();
+        xs.extend([p.x, p.x + 1]);
+        ys.extend([p.y, p.y + 1]);
+    }
+
+    Compression {
+        xs: boundaries(xs),
+        ys: boundaries(ys),
+    }
+}
+
+fn boundaries(mut cs: Vec<i64>) -> Vec<i64> {
+    cs.sort_unstable();
+    cs.dedup();
+    if let (Some(&first), Some(&last)) = (cs.first(), cs.last()) {
+        cs.insert(0, first - 1);
+        cs.push(last + 1);
+    }
+
+    cs
+}
+
+impl Compression {
+    // number of compressed columns
+    pub fn width(&self) -> i64 {
+        self.xs.len().saturating_sub(1) as i64
+    }
+
+    // number of compressed rows
+    pub fn height(&self) -> i64 {
+        self.ys.len().saturating_sub(1) as i64
+    }
+
+    // the compressed cell covering the real position, None outside of the
+    // border
+    pub fn index<P>(&self, p: P) -> Option<Point>
+    where
+        P: Into<Point>,
+    {
+        let p = p.into();
+        Some(Point::new(find(&self.xs, p.x)?, find(&self.ys, p.y)?))
+    }
+
+    // the real positions covered by a compressed cell, which must be inside
+    // 0..width() x 0..height() (so must the cells given to origin, weight and
+    // area)
+    pub fn rect<P>(&self, cell: P) -> Rect
+    where
+        P: Into<Point>,
+    {
+        let c = cell.into();
+        assert!(
+            (0..self.width()).contains(&c.x) && (0..self.height()).contains(&c.y),
+            "cell {:?} is outside of 0..{} x 0..{}",
+            c,
+            self.width(),
+            self.height()
+        );
+        let (x, y) = (c.x as usize, c.y as usize);
+        Rect::new(
+            self.xs[x],
+            self.ys[y],
+            self.xs[x + 1] - 1,
+            self.ys[y + 1] - 1,
+        )
+    }
+
+    // the top left real position of a compressed cell
+    pub fn origin<P>(&self, cell: P) -> Point
+    where
+        P: Into<Point>,
+    {
+        let r = self.rect(cell);
+        Point::new(r.min_x, r.min_y)
+    }
+
+    // how many real positions a compressed cell stands for
+    pub fn weight<P>(&self, cell: P) -> i64
+    where
+        P: Into<Point>,
+    {
+        let r = self.rect(cell);
+        r.width() * r.height()
+    }
+
+    // the real area of a set of compressed cells
+    pub fn area<I, P>(&self, cells: I) -> i64
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Point>,
+    {
+        cells.into_iter().map(|c| self.weight(c)).sum()
+    }
+
+    // a grid with every compressed cell set to fill
+    pub fn grid<T>(&self, fill: T) -> Grid<T>
+    where
+        T: Copy,
+    {
+        let mut grid = Grid::empty();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                grid.set(fill, x, y);
+            }
+        }
+
+        grid
+    }
+}
+
+// the column or row starting at or before c
+fn find(cs: &[i64], c: i64) -> Option<i64> {
+    if cs.len() < 2 || c < cs[0] || c >= cs[cs.len() - 1] {
+        return None;
+    }
+
+    match cs.binary_search(&c) {
+        Ok(i) => Some(i as i64),
+        Err(i) => Some(i as i64 - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::flood_fill;
+
+    #[test]
+    fn test_index() {
+        let c = compress([(0, 0), (1_000_000, 10)]);
+        // columns: border, 0, gap, 1_000_000, border
+        assert_eq!(5, c.width());
+        assert_eq!(5, c.height());
+        assert_eq!(Some(Point::new(1, 1)), c.index((0, 0)));
+        assert_eq!(Some(Point::new(2, 2)), c.index((500, 5)));
+        assert_eq!(Some(Point::new(3, 3)), c.index((1_000_000, 10)));
+        assert_eq!(None, c.index((1_000_002, 10)));
+
+        assert_eq!(Rect::new(1, 1, 999_999, 9), c.rect((2, 2)));
+        assert_eq!(Point::new(1, 1), c.origin((2, 2)));
+        assert_eq!(999_999 * 9, c.weight((2, 2)));
+        assert_eq!(1, c.weight((0, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of 0..5 x 0..5")]
+    fn test_rect_outside() {
+        let c = compress([(0, 0), (1_000_000, 10)]);
+        c.rect((5, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of 0..5 x 0..5")]
+    fn test_weight_negative() {
+        let c = compress([(0, 0), (1_000_000, 10)]);
+        c.weight((0, -1));
+    }
+
+    #[test]
+    fn test_fill() {
+        // a dig plan loop whose real area is 3_000_001 * 2_000_001
+        let corners = [
+            (0, 0),
+            (3_000_000, 0),
+            (3_000_000, 2_000_000),
+            (0, 2_000_000),
+        ];
+        let c = compress(corners);
+        let mut grid = c.grid('.');
+        for i in 0..corners.len() {
+            let a = c.index(corners[i]).unwrap();
+            let b = c.index(corners[(i + 1) % corners.len()]).unwrap();
+            grid.draw_line(a, b, '#');
+        }
+
+        let outside = flood_fill((0, 0), |p| grid.get_point(p) == Some('.'));
+        let total = c.area(grid.data.keys().copied());
+        assert_eq!(3_000_001 * 2_000_001, total - c.area(outside));
+    }
+}
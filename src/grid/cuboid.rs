@@ -0,0 +1,227 @@
+use std::cmp::{max, min};
+use std::str::FromStr;
+
+use regex::Regex;
+
+// an axis aligned box with inclusive bounds, the 3D counterpart of Rect
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min_x: i64,
+    pub min_y: i64,
+    pub min_z: i64,
+    pub max_x: i64,
+    pub max_y: i64,
+    pub max_z: i64,
+}
+
+impl Cuboid {
+    pub fn new(min_x: i64, min_y: i64, min_z: i64, max_x: i64, max_y: i64, max_z: i64) -> Cuboid {
+        Cuboid {
+            min_x,
+            min_y,
+            min_z,
+            max_x,
+            max_y,
+            max_z,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max_x < self.min_x || self.max_y < self.min_y || self.max_z < self.min_z
+    }
+
+    // number of positions covered
+    pub fn volume(&self) -> i64 {
+        if self.is_empty() {
+            0
+        } else {
+            (self.max_x - self.min_x + 1)
+                * (self.max_y - self.min_y + 1)
+                * (self.max_z - self.min_z + 1)
+        }
+    }
+
+    pub fn contains(&self, x: i64, y: i64, z: i64) -> bool {
+        x >= self.min_x
+            && x <= self.max_x
+            && y >= self.min_y
+            && y <= self.max_y
+            && z >= self.min_z
+            && z <= self.max_z
+    }
+
+    pub fn overlaps(&self, other: &Cuboid) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let c = Cuboid::new(
+            max(self.min_x, other.min_x),
+            max(self.min_y, other.min_y),
+            max(self.min_z, other.min_z),
+            min(self.max_x, other.max_x),
+            min(self.max_y, other.max_y),
+            min(self.max_z, other.max_z),
+        );
+
+        if c.is_empty() {
+            None
+        } else {
+            Some(c)
+        }
+    }
+
+    // the positions of self that aren't in other, as at most 6 disjoint
+    // cuboids: slabs beside the overlap along x, then along y within its x
+    // range, then along z within its x and y range
+    pub fn subtract(&self, other: &Cuboid) -> Vec<Cuboid> {
+        let i = match self.intersection(other) {
+            Some(i) => i,
+            None => return vec![*self],
+        };
+
+        let s = self;
+        let pieces = [
+            Cuboid::new(s.min_x, s.min_y, s.min_z, i.min_x - 1, s.max_y, s.max_z),
+            Cuboid::new(i.max_x + 1, s.min_y, s.min_z, s.max_x, s.max_y, s.max_z),
+            Cuboid::new(i.min_x, s.min_y, s.min_z, i.max_x, i.min_y - 1, s.max_z),
+            Cuboid::new(i.min_x, i.max_y + 1, s.min_z, i.max_x, s.max_y, s.max_z),
+            Cuboid::new(i.min_x, i.min_y, s.min_z, i.max_x, i.max_y, i.min_z - 1),
+            Cuboid::new(i.min_x, i.min_y, i.max_z + 1, i.max_x, i.max_y, s.max_z),
+        ];
+
+        pieces.into_iter().filter(|c| !c.is_empty()).collect()
+    }
+}
+
+impl FromStr for Cuboid {
+    type Err = String;
+
+    // x=10..12,y=-3..5,z=0..0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex =
+                Regex::new(r"^x=(-?\d+)\.\.(-?\d+),y=(-?\d+)\.\.(-?\d+),z=(-?\d+)\.\.(-?\d+)$")
+                    .unwrap();
+        }
+
+        let caps = RE
+            .captures(s.trim())
+            .ok_or(format!("not a cuboid: {}", s))?;
+        let c = (1..=6)
+            .map(|i| {
+                caps[i]
+                    .parse::<i64>()
+                    .map_err(|e| format!("bad coordinate {} in {}: {}", &caps[i], s, e))
+            })
+            .collect::<Result<Vec<i64>, String>>()?;
+
+        Ok(Cuboid::new(
+            min(c[0], c[1]),
+            min(c[2], c[3]),
+            min(c[4], c[5]),
+            max(c[0], c[1]),
+            max(c[2], c[3]),
+            max(c[4], c[5]),
+        ))
+    }
+}
+
+// a union of cuboids, kept as disjoint pieces so its volume is a plain sum
+#[derive(Clone, Debug, Default)]
+pub struct CuboidSet {
+    cuboids: Vec<Cuboid>,
+}
+
+impl CuboidSet {
+    pub fn new() -> CuboidSet {
+        CuboidSet { cuboids: vec![] }
+    }
+
+    // turn every position of c on
+    pub fn insert(&mut self, c: Cuboid) {
+        self.remove(&c);
+        if !c.is_empty() {
+            self.cuboids.push(c);
+        }
+    }
+
+    // turn every position of c off
+    pub fn remove(&mut self, c: &Cuboid) {
+        self.cuboids = self.cuboids.iter().flat_map(|p| p.subtract(c)).collect();
+    }
+
+    pub fn set(&mut self, c: Cuboid, on: bool) {
+        if on {
+            self.insert(c);
+        } else {
+            self.remove(&c);
+        }
+    }
+
+    pub fn contains(&self, x: i64, y: i64, z: i64) -> bool {
+        self.cuboids.iter().any(|c| c.contains(x, y, z))
+    }
+
+    // number of positions that are on
+    pub fn volume(&self) -> i64 {
+        self.cuboids.iter().map(|c| c.volume()).sum()
+    }
+
+    // the disjoint pieces making up the set
+    pub fn cuboids(&self) -> &[Cuboid] {
+        &self.cuboids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(Cuboid::new(10, -3, 0, 12, 5, 0)),
+            "x=10..12,y=5..-3,z=0..0".parse()
+        );
+        assert!("x=1..2,y=3..4".parse::<Cuboid>().is_err());
+        assert!("x=1..99999999999999999999,y=3..4,z=5..6"
+            .parse::<Cuboid>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = Cuboid::new(0, 0, 0, 9, 9, 9);
+        let hole = Cuboid::new(3, 3, 3, 5, 5, 5);
+
+        let pieces = a.subtract(&hole);
+        assert_eq!(6, pieces.len());
+        assert_eq!(1000 - 27, pieces.iter().map(|c| c.volume()).sum::<i64>());
+        assert!(pieces.iter().all(|c| !c.overlaps(&hole)));
+
+        assert_eq!(vec![a], a.subtract(&Cuboid::new(10, 0, 0, 12, 9, 9)));
+        assert!(a.subtract(&a).is_empty());
+        assert_eq!(Some(hole), a.intersection(&hole));
+    }
+
+    #[test]
+    fn test_reactor_reboot() {
+        let steps = [
+            (true, "x=10..12,y=10..12,z=10..12"),
+            (true, "x=11..13,y=11..13,z=11..13"),
+            (false, "x=9..11,y=9..11,z=9..11"),
+            (true, "x=10..10,y=10..10,z=10..10"),
+        ];
+
+        let mut set = CuboidSet::new();
+        for (on, c) in steps {
+            set.set(c.parse().unwrap(), on);
+        }
+
+        assert_eq!(39, set.volume());
+        assert!(set.contains(10, 10, 10));
+        assert!(!set.contains(11, 11, 11));
+        assert!(set.contains(13, 13, 13));
+    }
+}
@@ -0,0 +1,269 @@
+use std::ops::{Index, IndexMut};
+
+use super::Grid;
+
+// a rectangular grid stored row by row in a single vec, with (0, 0) in the top
+// left corner, for inputs where every cell is known and lookups are hot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenseGrid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> DenseGrid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> DenseGrid<T>
+    where
+        T: Clone,
+    {
+        DenseGrid {
+            data: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    // copy the bounding box of the sparse grid's cells, with its top left cell
+    // placed at (0, 0) and missing cells set to fill
+    pub fn from_grid(grid: &Grid<T>, fill: T) -> DenseGrid<T>
+    where
+        T: Copy,
+    {
+        let bounds = grid.bounds();
+        let mut dense = DenseGrid::new(bounds.width() as usize, bounds.height() as usize, fill);
+        for (pos, value) in grid.data.iter() {
+            dense[(
+                (pos.0 - bounds.min_x) as usize,
+                (pos.1 - bounds.min_y) as usize,
+            )] = *value;
+        }
+
+        dense
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Copy,
+    {
+        let mut grid = Grid::empty();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                grid.set(self[(x, y)], x as i64, y as i64);
+            }
+        }
+
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<T>
+    where
+        T: Copy,
+    {
+        if self.contains(x, y) {
+            Some(self.data[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, input: T, x: i64, y: i64) {
+        assert!(
+            self.contains(x, y),
+            "({}, {}) is outside of {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        self.data[y as usize * self.width + x as usize] = input;
+    }
+
+    /// # Safety
+    ///
+    /// `x` must be less than the width and `y` less than the height.
+    pub unsafe fn get_unchecked(&self, x: usize, y: usize) -> &T {
+        self.data.get_unchecked(y * self.width + x)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on zero, an empty grid has no rows either way
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} outside of grid", x);
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    // position in data, checking both coordinates so that an x past the end
+    // of a row doesn't wrap into the next one
+    fn offset(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        y * self.width + x
+    }
+
+    pub fn walk<F>(&self, mut visitor: F)
+    where
+        T: Copy,
+        F: FnMut((i64, i64), T),
+    {
+        for (i, v) in self.data.iter().enumerate() {
+            visitor(((i % self.width) as i64, (i / self.width) as i64), *v)
+        }
+    }
+}
+
+impl DenseGrid<char> {
+    // as wide as the longest line, with shorter lines padded on the right with
+    // fill where the sparse Grid::new would leave cells unset
+    pub fn from_lines(input: &[String], fill: char) -> DenseGrid<char> {
+        let height = input.len();
+        let width = input.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut data = Vec::with_capacity(width * height);
+
+        for line in input {
+            let len = data.len();
+            data.extend(line.chars());
+            data.resize(len + width, fill);
+        }
+
+        DenseGrid {
+            data,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for DenseGrid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self.data[self.offset(x, y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for DenseGrid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let i = self.offset(x, y);
+        &mut self.data[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_input() -> Vec<String> {
+        vec!["#..".to_string(), ".#.".to_string()]
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let grid = DenseGrid::from_lines(&get_input(), ' ');
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(Some('#'), grid.get(0, 0));
+        assert_eq!(Some('#'), grid.get(1, 1));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(None, grid.get(-1, 0));
+        assert_eq!('.', grid[(2, 1)]);
+        assert_eq!('#', unsafe { *grid.get_unchecked(1, 1) });
+    }
+
+    #[test]
+    fn test_from_ragged_lines() {
+        let input = vec!["#.".to_string(), "".to_string(), ".#.#".to_string()];
+        let grid = DenseGrid::from_lines(&input, ' ');
+
+        assert_eq!(4, grid.width());
+        assert_eq!(3, grid.height());
+        assert_eq!(&['#', '.', ' ', ' '], grid.row(0));
+        assert_eq!(&[' ', ' ', ' ', ' '], grid.row(1));
+        assert_eq!(Some('#'), grid.get(3, 2));
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid = DenseGrid::from_lines(&[], ' ');
+        assert_eq!(0, grid.width());
+        assert_eq!(0, grid.rows().count());
+        assert_eq!(None, grid.get(0, 0));
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid = DenseGrid::new(4, 3, 0);
+        grid.set(7, 3, 2);
+        grid[(1, 0)] = 5;
+        grid.row_mut(1)[0] = 2;
+
+        assert_eq!(Some(7), grid.get(3, 2));
+        assert_eq!(&[0, 5, 0, 0], grid.row(0));
+        assert_eq!(vec![&5, &0, &0], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(vec![&0, &2, &0], grid.column(0).collect::<Vec<_>>());
+
+        let mut sum = 0;
+        grid.walk(|pos, v| sum += v * (pos.0 + pos.1));
+        assert_eq!(7 * 5 + 5 + 2, sum);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_outside() {
+        let mut grid = DenseGrid::new(4, 3, 0);
+        grid.set(7, 4, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_outside_row() {
+        let grid = DenseGrid::new(4, 3, 0);
+        let _ = grid[(5, 0)];
+    }
+
+    #[test]
+    fn test_grid_conversion() {
+        let dense = DenseGrid::from_lines(&get_input(), ' ');
+        let sparse = dense.to_grid();
+        assert_eq!(Some('#'), sparse.get(1, 1));
+        assert_eq!(dense, DenseGrid::from_grid(&sparse, ' '));
+
+        let mut sparse = Grid::empty();
+        sparse.set(1, -2, -1);
+        sparse.set(2, 1, 1);
+        let dense = DenseGrid::from_grid(&sparse, 0);
+        assert_eq!(4, dense.width());
+        assert_eq!(3, dense.height());
+        assert_eq!(&[1, 0, 0, 0], dense.row(0));
+        assert_eq!(&[0, 0, 0, 2], dense.row(2));
+    }
+}
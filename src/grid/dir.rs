@@ -0,0 +1,232 @@
+use std::str::FromStr;
+
+use super::Point;
+
+// Directions are named as seen on screen. The grid has y growing downwards like
+// the rows of the input, so offset() of Up is (0, -1). Puzzles that treat up as
+// y + 1 should use offset_y_up() instead.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    // clockwise, starting at up
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn iter() -> impl Iterator<Item = Dir4> {
+        Dir4::ALL.into_iter()
+    }
+
+    pub fn turn_right(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn offset(self) -> Point {
+        match self {
+            Dir4::Up => Point::new(0, -1),
+            Dir4::Right => Point::new(1, 0),
+            Dir4::Down => Point::new(0, 1),
+            Dir4::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn offset_y_up(self) -> Point {
+        let o = self.offset();
+        Point::new(o.x, -o.y)
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Dir4, String> {
+        match c {
+            'U' | 'N' | '^' => Ok(Dir4::Up),
+            'R' | 'E' | '>' => Ok(Dir4::Right),
+            'D' | 'S' | 'v' => Ok(Dir4::Down),
+            'L' | 'W' | '<' => Ok(Dir4::Left),
+            _ => Err(format!("not a direction: {}", c)),
+        }
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Dir4, String> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Dir4::try_from(c),
+            _ => Err(format!("not a direction: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    // clockwise, starting at up
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Dir8> {
+        Dir8::ALL.into_iter()
+    }
+
+    // 90° turns, like Dir4
+    pub fn turn_right(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 2) % 8]
+    }
+
+    pub fn turn_left(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 6) % 8]
+    }
+
+    pub fn turn_right_45(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn turn_left_45(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub fn offset(self) -> Point {
+        match self {
+            Dir8::Up => Point::new(0, -1),
+            Dir8::UpRight => Point::new(1, -1),
+            Dir8::Right => Point::new(1, 0),
+            Dir8::DownRight => Point::new(1, 1),
+            Dir8::Down => Point::new(0, 1),
+            Dir8::DownLeft => Point::new(-1, 1),
+            Dir8::Left => Point::new(-1, 0),
+            Dir8::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    pub fn offset_y_up(self) -> Point {
+        let o = self.offset();
+        Point::new(o.x, -o.y)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(d: Dir4) -> Dir8 {
+        Dir8::ALL[d as usize * 2]
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Dir8, String> {
+        match s {
+            "U" | "N" | "^" => Ok(Dir8::Up),
+            "UR" | "NE" => Ok(Dir8::UpRight),
+            "R" | "E" | ">" => Ok(Dir8::Right),
+            "DR" | "SE" => Ok(Dir8::DownRight),
+            "D" | "S" | "v" => Ok(Dir8::Down),
+            "DL" | "SW" => Ok(Dir8::DownLeft),
+            "L" | "W" | "<" => Ok(Dir8::Left),
+            "UL" | "NW" => Ok(Dir8::UpLeft),
+            _ => Err(format!("not a direction: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir4_turns() {
+        assert_eq!(Dir4::Right, Dir4::Up.turn_right());
+        assert_eq!(Dir4::Left, Dir4::Up.turn_left());
+        assert_eq!(Dir4::Down, Dir4::Up.reverse());
+        assert_eq!(Dir4::Up, Dir4::Left.turn_right());
+        for d in Dir4::iter() {
+            assert_eq!(d, d.turn_left().turn_right());
+            assert_eq!(d.offset().rotate_cw(), d.turn_right().offset());
+            assert_eq!(-d.offset(), d.reverse().offset());
+        }
+        assert_eq!(4, Dir4::iter().count());
+    }
+
+    #[test]
+    fn test_dir4_parse() {
+        for s in ["U", "N", "^"] {
+            assert_eq!(Ok(Dir4::Up), s.parse());
+        }
+        for c in ['D', 'S', 'v'] {
+            assert_eq!(Ok(Dir4::Down), Dir4::try_from(c));
+        }
+        assert_eq!(Ok(Dir4::Left), "<".parse());
+        assert_eq!(Ok(Dir4::Right), "E".parse());
+        assert!("X".parse::<Dir4>().is_err());
+        assert!("UU".parse::<Dir4>().is_err());
+        assert!("".parse::<Dir4>().is_err());
+    }
+
+    #[test]
+    fn test_axis_convention() {
+        assert_eq!(Point::new(0, -1), Dir4::Up.offset());
+        assert_eq!(Point::new(0, 1), Dir4::Up.offset_y_up());
+        assert_eq!(Point::new(1, 0), Dir4::Right.offset_y_up());
+        assert_eq!(Point::new(1, 1), Dir8::UpRight.offset_y_up());
+    }
+
+    #[test]
+    fn test_dir8() {
+        assert_eq!(Dir8::Right, Dir8::Up.turn_right());
+        assert_eq!(Dir8::UpRight, Dir8::Up.turn_right_45());
+        assert_eq!(Dir8::UpLeft, Dir8::Up.turn_left_45());
+        assert_eq!(Dir8::DownLeft, Dir8::UpRight.reverse());
+        assert_eq!(Ok(Dir8::DownLeft), "SW".parse());
+        assert_eq!(4, Dir8::iter().filter(|d| d.is_diagonal()).count());
+        for d in Dir4::iter() {
+            assert_eq!(d.offset(), Dir8::from(d).offset());
+        }
+        for d in Dir8::iter() {
+            assert_eq!(1, d.offset().chebyshev(Point::new(0, 0)));
+            assert_eq!(-d.offset(), d.reverse().offset());
+        }
+    }
+}
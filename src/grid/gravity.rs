@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use super::{Cuboid, Dir4, Dir8, Grid, Point};
+
+impl<T> Grid<T>
+where
+    T: Copy + PartialEq,
+{
+    // slide every movable cell in dir until it hits a blocker, another
+    // movable cell or the edge, returning how many cells moved. Cells that are
+    // neither are free space and swap places with whatever moves into them.
+    pub fn tilt(&mut self, dir: Dir4, movable: T, blocker: T) -> usize {
+        let step = dir.offset();
+        let mut cells: Vec<Point> = self
+            .data
+            .iter()
+            .filter(|(_, v)| **v == movable)
+            .map(|(p, _)| Point::from(*p))
+            .collect();
+        // the cells furthest along dir settle first
+        cells.sort_by_key(|p| -(p.x * step.x + p.y * step.y));
+
+        let mut moved = 0;
+        for start in cells {
+            let mut pos = start;
+            while let Some(v) = self.get_point(pos + step) {
+                if v == movable || v == blocker {
+                    break;
+                }
+                pos += step;
+            }
+
+            if pos != start {
+                let space = self.get_point(pos).unwrap();
+                self.set_point(movable, pos);
+                self.set_point(space, start);
+                moved += 1;
+            }
+        }
+
+        moved
+    }
+
+    // where a particle dropped at start comes to rest, trying the moves in
+    // order (e.g. down, down left, down right for sand) for as long as one of
+    // them leads somewhere is_free, None once it falls below abyss_y. Every
+    // move must go down, so that the particle can't go round in circles.
+    pub fn drop_particle<P, F>(
+        &self,
+        start: P,
+        moves: &[Dir8],
+        is_free: F,
+        abyss_y: i64,
+    ) -> Option<Point>
+    where
+        P: Into<Point>,
+        F: Fn(Point, Option<T>) -> bool,
+    {
+        assert!(
+            moves.iter().all(|m| m.offset().y > 0),
+            "moves must all go down: {:?}",
+            moves
+        );
+
+        let mut pos = start.into();
+        loop {
+            if pos.y > abyss_y {
+                return None;
+            }
+
+            let next = moves
+                .iter()
+                .map(|m| pos + m.offset())
+                .find(|p| is_free(*p, self.get_point(*p)));
+            match next {
+                Some(p) => pos = p,
+                None => return Some(pos),
+            }
+        }
+    }
+
+    // drop particles at start one by one, setting value where each comes to
+    // rest, until one is lost below abyss_y or start itself is no longer
+    // free, returning how many came to rest
+    pub fn pour<P, F>(
+        &mut self,
+        start: P,
+        moves: &[Dir8],
+        value: T,
+        is_free: F,
+        abyss_y: i64,
+    ) -> usize
+    where
+        P: Into<Point>,
+        F: Fn(Point, Option<T>) -> bool,
+    {
+        let start = start.into();
+        let mut count = 0;
+        while is_free(start, self.get_point(start)) {
+            match self.drop_particle(start, moves, &is_free, abyss_y) {
+                Some(p) => {
+                    self.set_point(value, p);
+                    count += 1;
+                }
+                None => break,
+            }
+        }
+
+        count
+    }
+}
+
+// bricks after falling as far down as they can, with z = 0 as the ground,
+// indices refer to the order the bricks were given in
+pub struct SettledBricks {
+    pub bricks: Vec<Cuboid>,
+    // the bricks resting directly on each brick
+    pub supports: HashMap<usize, Vec<usize>>,
+    // the bricks each brick rests directly on, empty for the ground
+    pub supported_by: HashMap<usize, Vec<usize>>,
+}
+
+impl SettledBricks {
+    // whether removing the brick would leave the bricks above it in place
+    pub fn is_removable(&self, i: usize) -> bool {
+        self.supports[&i]
+            .iter()
+            .all(|above| self.supported_by[above].len() > 1)
+    }
+}
+
+// let the bricks fall straight down, lowest first, until they land on the
+// ground or another brick
+pub fn settle_bricks(bricks: &[Cuboid]) -> SettledBricks {
+    let mut order: Vec<usize> = (0..bricks.len()).collect();
+    order.sort_by_key(|i| bricks[*i].min_z);
+
+    // the top brick and its height above each column
+    let mut tops: HashMap<(i64, i64), (i64, usize)> = HashMap::new();
+    let mut settled = bricks.to_vec();
+    let mut supports: HashMap<usize, Vec<usize>> = (0..bricks.len()).map(|i| (i, vec![])).collect();
+    let mut supported_by = supports.clone();
+
+    for i in order {
+        let b = bricks[i];
+        let columns: Vec<(i64, i64)> = (b.min_x..=b.max_x)
+            .flat_map(|x| (b.min_y..=b.max_y).map(move |y| (x, y)))
+            .collect();
+
+        let floor = columns
+            .iter()
+            .filter_map(|c| tops.get(c).map(|(z, _)| *z))
+            .max()
+            .unwrap_or(0);
+        let mut below: Vec<usize> = columns
+            .iter()
+            .filter_map(|c| tops.get(c))
+            .filter(|(z, _)| *z == floor)
+            .map(|(_, j)| *j)
+            .collect();
+        below.sort_unstable();
+        below.dedup();
+
+        let drop = b.min_z - floor - 1;
+        let landed = Cuboid::new(
+            b.min_x,
+            b.min_y,
+            b.min_z - drop,
+            b.max_x,
+            b.max_y,
+            b.max_z - drop,
+        );
+        for c in columns {
+            tops.insert(c, (landed.max_z, i));
+        }
+        for j in below.iter() {
+            supports.get_mut(j).unwrap().push(i);
+        }
+        supported_by.insert(i, below);
+        settled[i] = landed;
+    }
+
+    SettledBricks {
+        bricks: settled,
+        supports,
+        supported_by,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tilt() {
+        // parabolic reflector dish example, a load of 136 after tilting north
+        let mut grid = Grid::new(&[
+            "O....#....".to_string(),
+            "O.OO#....#".to_string(),
+            ".....##...".to_string(),
+            "OO.#O....O".to_string(),
+            ".O.....O#.".to_string(),
+            "O.#..O.#.#".to_string(),
+            "..O..#O..O".to_string(),
+            ".......O..".to_string(),
+            "#....###..".to_string(),
+            "#OO..#....".to_string(),
+        ]);
+        assert!(grid.tilt(Dir4::Up, 'O', '#') > 0);
+
+        let mut load = 0;
+        let height = grid.height();
+        grid.walk(|(_, y), c| {
+            if c == 'O' {
+                load += height - y;
+            }
+        });
+        assert_eq!(136, load);
+        assert_eq!(0, grid.tilt(Dir4::Up, 'O', '#'));
+
+        let mut row = Grid::new(&["O.O#.O".to_string()]);
+        row.tilt(Dir4::Right, 'O', '#');
+        assert_eq!(".OO#.O", row.to_string());
+    }
+
+    fn rocks() -> Grid<char> {
+        let mut grid = Grid::empty();
+        grid.draw_path(&[(498, 4), (498, 6), (496, 6)], '#');
+        grid.draw_path(&[(503, 4), (502, 4), (502, 9), (494, 9)], '#');
+        grid
+    }
+
+    const SAND: [Dir8; 3] = [Dir8::Down, Dir8::DownLeft, Dir8::DownRight];
+
+    #[test]
+    fn test_pour() {
+        // regolith reservoir example
+        let mut grid = rocks();
+        let abyss = grid.max_y();
+        assert_eq!(
+            Some(Point::new(500, 8)),
+            grid.drop_particle((500, 0), &SAND, |_, v| v.is_none(), abyss)
+        );
+        assert_eq!(
+            24,
+            grid.pour((500, 0), &SAND, 'o', |_, v| v.is_none(), abyss)
+        );
+
+        // with a floor two below the lowest rock the source gets blocked
+        let mut grid = rocks();
+        let floor = grid.max_y() + 2;
+        let is_free = |p: Point, v: Option<char>| v.is_none() && p.y < floor;
+        assert_eq!(93, grid.pour((500, 0), &SAND, 'o', is_free, floor));
+    }
+
+    #[test]
+    #[should_panic(expected = "moves must all go down")]
+    fn test_drop_sideways() {
+        let grid = rocks();
+        grid.drop_particle((500, 0), &[Dir8::Left, Dir8::Right], |_, v| v.is_none(), 20);
+    }
+
+    #[test]
+    fn test_settle_bricks() {
+        // sand slabs example, 5 bricks can be removed on their own
+        let bricks = [
+            Cuboid::new(1, 0, 1, 1, 2, 1),
+            Cuboid::new(0, 0, 2, 2, 0, 2),
+            Cuboid::new(0, 2, 3, 2, 2, 3),
+            Cuboid::new(0, 0, 4, 0, 2, 4),
+            Cuboid::new(2, 0, 5, 2, 2, 5),
+            Cuboid::new(0, 1, 6, 2, 1, 6),
+            Cuboid::new(1, 1, 8, 1, 1, 9),
+        ];
+        let settled = settle_bricks(&bricks);
+
+        assert_eq!(
+            5,
+            (0..bricks.len())
+                .filter(|i| settled.is_removable(*i))
+                .count()
+        );
+        assert_eq!(vec![1, 2], settled.supports[&0]);
+        assert_eq!(vec![3, 4], settled.supported_by[&5]);
+        assert_eq!(Cuboid::new(1, 1, 5, 1, 1, 6), settled.bricks[6]);
+        assert!(settled.supported_by[&0].is_empty());
+    }
+}
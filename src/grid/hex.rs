@@ -1,4 +1,7 @@
+use std::cmp::{max, min};
 use std::collections::HashMap;
+use std::ops::{Add, AddAssign, Mul, Sub};
+use std::str::FromStr;
 
 //   \ n  /
 // nw +--+ ne
@@ -36,6 +39,332 @@ pub fn hex_cube_dist(a: (i64, i64, i64), b: (i64, i64, i64)) -> i64 {
     (diff.0.abs() + diff.1.abs() + diff.2.abs()) / 2
 }
 
+// Cube coordinates as used on https://www.redblobgames.com/grids/hexagons/,
+// always q + r + s == 0. Axial coordinates are (q, r).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+    pub s: i64,
+}
+
+// the ways of storing hexes in a rectangle, rows (r) belong to pointy top
+// layouts and columns (q) to flat top ones, odd/even says which rows or
+// columns are shoved half a hex over
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OffsetLayout {
+    OddR,
+    EvenR,
+    OddQ,
+    EvenQ,
+}
+
+impl Hex {
+    pub const fn new(q: i64, r: i64) -> Hex {
+        Hex { q, r, s: -q - r }
+    }
+
+    pub fn cube(q: i64, r: i64, s: i64) -> Hex {
+        assert_eq!(0, q + r + s, "cube coordinates must sum to zero");
+        Hex { q, r, s }
+    }
+
+    pub fn axial(self) -> (i64, i64) {
+        (self.q, self.r)
+    }
+
+    pub fn from_offset(col: i64, row: i64, layout: OffsetLayout) -> Hex {
+        match layout {
+            OffsetLayout::OddR => Hex::new(col - (row - (row & 1)) / 2, row),
+            OffsetLayout::EvenR => Hex::new(col - (row + (row & 1)) / 2, row),
+            OffsetLayout::OddQ => Hex::new(col, row - (col - (col & 1)) / 2),
+            OffsetLayout::EvenQ => Hex::new(col, row - (col + (col & 1)) / 2),
+        }
+    }
+
+    // (col, row)
+    pub fn to_offset(self, layout: OffsetLayout) -> (i64, i64) {
+        let (q, r) = (self.q, self.r);
+        match layout {
+            OffsetLayout::OddR => (q + (r - (r & 1)) / 2, r),
+            OffsetLayout::EvenR => (q + (r + (r & 1)) / 2, r),
+            OffsetLayout::OddQ => (q, r + (q - (q & 1)) / 2),
+            OffsetLayout::EvenQ => (q, r + (q + (q & 1)) / 2),
+        }
+    }
+
+    pub fn dist(self, other: Hex) -> i64 {
+        let diff = self - other;
+        (diff.q.abs() + diff.r.abs() + diff.s.abs()) / 2
+    }
+
+    // the 6 surrounding hexes, in the same order for either layout
+    pub fn neighbours(self) -> impl Iterator<Item = Hex> {
+        PointyDir::ALL.into_iter().map(move |d| self + d.offset())
+    }
+
+    // the hexes at exactly radius steps, going round from the south west
+    pub fn ring(self, radius: i64) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+
+        let mut ring = vec![];
+        let mut hex = self + PointyDir::SW.offset() * radius;
+        for d in PointyDir::ALL {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += d.offset();
+            }
+        }
+
+        ring
+    }
+
+    // the hexes within radius steps ring by ring, starting with self
+    pub fn spiral(self, radius: i64) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+
+    // the hexes within radius steps, ordered by q then r
+    pub fn range(self, radius: i64) -> Vec<Hex> {
+        let mut hexes = vec![];
+        for q in -radius..=radius {
+            for r in max(-radius, -q - radius)..=min(radius, -q + radius) {
+                hexes.push(self + Hex::new(q, r));
+            }
+        }
+
+        hexes
+    }
+}
+
+// HEX_OFFSETS and the hex_cube_* functions predate Hex and order their tuples
+// differently, this keeps their directions pointing the same way as FlatDir
+impl TryFrom<(i64, i64, i64)> for Hex {
+    type Error = String;
+
+    fn try_from(c: (i64, i64, i64)) -> Result<Hex, String> {
+        if c.0 + c.1 + c.2 != 0 {
+            return Err(format!("cube coordinates {:?} don't sum to 0", c));
+        }
+        Ok(Hex::cube(-c.2, -c.0, -c.1))
+    }
+}
+
+impl From<Hex> for (i64, i64, i64) {
+    fn from(h: Hex) -> (i64, i64, i64) {
+        (-h.r, -h.s, -h.q)
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::cube(self.q + other.q, self.r + other.r, self.s + other.s)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Hex) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::cube(self.q - other.q, self.r - other.r, self.s - other.s)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, factor: i64) -> Hex {
+        Hex::cube(self.q * factor, self.r * factor, self.s * factor)
+    }
+}
+
+// directions for hexes with a pointy top, e.g. the e/w/ne/nw/se/sw lobby layout
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointyDir {
+    E,
+    NE,
+    NW,
+    W,
+    SW,
+    SE,
+}
+
+impl PointyDir {
+    // counter clockwise, starting at east
+    pub const ALL: [PointyDir; 6] = [
+        PointyDir::E,
+        PointyDir::NE,
+        PointyDir::NW,
+        PointyDir::W,
+        PointyDir::SW,
+        PointyDir::SE,
+    ];
+
+    pub fn offset(self) -> Hex {
+        match self {
+            PointyDir::E => Hex::new(1, 0),
+            PointyDir::NE => Hex::new(1, -1),
+            PointyDir::NW => Hex::new(0, -1),
+            PointyDir::W => Hex::new(-1, 0),
+            PointyDir::SW => Hex::new(-1, 1),
+            PointyDir::SE => Hex::new(0, 1),
+        }
+    }
+
+    // split a run of directions without separators, e.g. "esenee"
+    pub fn parse_steps(s: &str) -> Result<Vec<PointyDir>, String> {
+        let mut steps = vec![];
+        let mut rest = s;
+        while !rest.is_empty() {
+            let len = if rest.starts_with('n') || rest.starts_with('s') {
+                2
+            } else {
+                1
+            };
+            let step = rest
+                .get(..len)
+                .ok_or(format!("not a direction: {}", rest))?;
+            steps.push(step.parse()?);
+            rest = &rest[len..];
+        }
+
+        Ok(steps)
+    }
+}
+
+impl FromStr for PointyDir {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<PointyDir, String> {
+        match s {
+            "e" => Ok(PointyDir::E),
+            "ne" => Ok(PointyDir::NE),
+            "nw" => Ok(PointyDir::NW),
+            "w" => Ok(PointyDir::W),
+            "sw" => Ok(PointyDir::SW),
+            "se" => Ok(PointyDir::SE),
+            _ => Err(format!("not a direction: {}", s)),
+        }
+    }
+}
+
+// directions for hexes with a flat top, the n/s/ne/nw/se/sw layout of
+// HEX_OFFSETS
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FlatDir {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl FlatDir {
+    // clockwise, starting at north
+    pub const ALL: [FlatDir; 6] = [
+        FlatDir::N,
+        FlatDir::NE,
+        FlatDir::SE,
+        FlatDir::S,
+        FlatDir::SW,
+        FlatDir::NW,
+    ];
+
+    pub fn offset(self) -> Hex {
+        match self {
+            FlatDir::N => Hex::new(0, -1),
+            FlatDir::NE => Hex::new(1, -1),
+            FlatDir::SE => Hex::new(1, 0),
+            FlatDir::S => Hex::new(0, 1),
+            FlatDir::SW => Hex::new(-1, 1),
+            FlatDir::NW => Hex::new(-1, 0),
+        }
+    }
+}
+
+impl FromStr for FlatDir {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<FlatDir, String> {
+        match s {
+            "n" => Ok(FlatDir::N),
+            "ne" => Ok(FlatDir::NE),
+            "se" => Ok(FlatDir::SE),
+            "s" => Ok(FlatDir::S),
+            "sw" => Ok(FlatDir::SW),
+            "nw" => Ok(FlatDir::NW),
+            _ => Err(format!("not a direction: {}", s)),
+        }
+    }
+}
+
+// the hex counterpart of Grid, storing only the cells that are set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    data: HashMap<Hex, T>,
+}
+
+impl<T> HexGrid<T> {
+    pub fn empty() -> HexGrid<T> {
+        HexGrid {
+            data: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, hex: Hex) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.data.get(&hex).copied()
+    }
+
+    pub fn set(&mut self, input: T, hex: Hex) {
+        self.data.insert(hex, input);
+    }
+
+    pub fn remove(&mut self, hex: Hex) -> Option<T> {
+        self.data.remove(&hex)
+    }
+
+    pub fn walk<F>(&self, mut visitor: F)
+    where
+        T: Copy,
+        F: FnMut(Hex, T),
+    {
+        for (k, v) in &self.data {
+            visitor(*k, *v)
+        }
+    }
+
+    // the set cells surrounding hex
+    pub fn neighbours(&self, hex: Hex) -> impl Iterator<Item = (Hex, T)> + '_
+    where
+        T: Copy,
+    {
+        hex.neighbours()
+            .filter_map(move |n| self.get(n).map(|v| (n, v)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,4 +389,111 @@ mod tests {
         );
         assert_eq!(2, result);
     }
+
+    #[test]
+    fn test_hex_offsets_compat() {
+        for (name, offset) in HEX_OFFSETS.iter() {
+            let dir: FlatDir = name.parse().unwrap();
+            assert_eq!(Ok(dir.offset()), Hex::try_from(*offset));
+            assert_eq!(*offset, dir.offset().into());
+        }
+        assert!(Hex::try_from((1, 1, 1)).is_err());
+    }
+
+    #[test]
+    fn test_hex() {
+        let a = Hex::new(1, -3);
+        assert_eq!(2, a.s);
+        assert_eq!((1, -3), a.axial());
+        assert_eq!(Hex::cube(2, -2, 0), a + Hex::new(1, 1));
+        assert_eq!(Hex::cube(-3, 9, -6), a * -3);
+        assert_eq!(3, a.dist(Hex::new(0, 0)));
+        assert_eq!(6, a.neighbours().count());
+        assert!(a.neighbours().all(|n| n.dist(a) == 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cube_sum() {
+        Hex::cube(1, 1, 1);
+    }
+
+    #[test]
+    fn test_offset() {
+        let layouts = [
+            OffsetLayout::OddR,
+            OffsetLayout::EvenR,
+            OffsetLayout::OddQ,
+            OffsetLayout::EvenQ,
+        ];
+        for layout in layouts {
+            for hex in Hex::new(0, 0).range(3) {
+                let (col, row) = hex.to_offset(layout);
+                assert_eq!(hex, Hex::from_offset(col, row, layout));
+            }
+        }
+
+        assert_eq!((0, 1), Hex::new(0, 1).to_offset(OffsetLayout::OddR));
+        assert_eq!((1, 1), Hex::new(0, 1).to_offset(OffsetLayout::EvenR));
+        assert_eq!((1, -1), Hex::new(1, -1).to_offset(OffsetLayout::OddQ));
+        assert_eq!((1, 0), Hex::new(1, -1).to_offset(OffsetLayout::EvenQ));
+    }
+
+    #[test]
+    fn test_ring_spiral_range() {
+        let center = Hex::new(2, -1);
+        assert_eq!(vec![center], center.ring(0));
+
+        let ring = center.ring(2);
+        assert_eq!(12, ring.len());
+        assert!(ring.iter().all(|h| h.dist(center) == 2));
+
+        let spiral = center.spiral(2);
+        assert_eq!(19, spiral.len());
+        assert_eq!(center, spiral[0]);
+
+        let range = center.range(2);
+        assert_eq!(19, range.len());
+        assert!(spiral.iter().all(|h| range.contains(h)));
+    }
+
+    #[test]
+    fn test_parse_steps() {
+        // lobby layout example, esew flips the tile just south east of the reference
+        let steps = PointyDir::parse_steps("esew").unwrap();
+        assert_eq!(vec![PointyDir::E, PointyDir::SE, PointyDir::W], steps);
+
+        let hex = steps.iter().fold(Hex::new(0, 0), |h, d| h + d.offset());
+        assert_eq!(PointyDir::SE.offset(), hex);
+
+        let home = PointyDir::parse_steps("nwwswee")
+            .unwrap()
+            .iter()
+            .fold(Hex::new(0, 0), |h, d| h + d.offset());
+        assert_eq!(Hex::new(0, 0), home);
+
+        assert!(PointyDir::parse_steps("ex").is_err());
+        assert!(PointyDir::parse_steps("n").is_err());
+    }
+
+    #[test]
+    fn test_hex_grid() {
+        let mut grid = HexGrid::empty();
+        assert!(grid.is_empty());
+
+        let center = Hex::new(0, 0);
+        grid.set(true, center);
+        grid.set(true, PointyDir::E.offset());
+        grid.set(false, PointyDir::W.offset());
+
+        assert_eq!(3, grid.len());
+        assert_eq!(Some(true), grid.get(center));
+        assert_eq!(2, grid.neighbours(center).count());
+        assert_eq!(1, grid.neighbours(center).filter(|(_, v)| *v).count());
+        assert_eq!(Some(false), grid.remove(PointyDir::W.offset()));
+
+        let mut count = 0;
+        grid.walk(|_, v| count += v as i64);
+        assert_eq!(2, count);
+    }
 }
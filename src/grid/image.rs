@@ -0,0 +1,246 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{Grid, Rect};
+
+// Netpbm images are a short text header followed by raw pixels, which any
+// image viewer (or ffmpeg) understands without needing an encoder here. Every
+// cell becomes a scale x scale block of pixels, unset cells included.
+
+impl<T> Grid<T>
+where
+    T: Copy,
+{
+    // colour image, one (r, g, b) per cell
+    pub fn write_ppm<P, F>(&self, path: P, scale: usize, color: F) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(Option<T>) -> (u8, u8, u8),
+    {
+        fs::write(path, self.to_ppm(self.bounds, scale, color))
+    }
+
+    // greyscale image, 0 is black and 255 white
+    pub fn write_pgm<P, F>(&self, path: P, scale: usize, grey: F) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(Option<T>) -> u8,
+    {
+        fs::write(path, self.to_pgm(self.bounds, scale, grey))
+    }
+
+    // black and white image, cells for which is_black holds are drawn black
+    pub fn write_pbm<P, F>(&self, path: P, scale: usize, is_black: F) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(Option<T>) -> bool,
+    {
+        fs::write(path, self.to_pbm(self.bounds, scale, is_black))
+    }
+
+    fn to_ppm<F>(&self, bounds: Rect, scale: usize, color: F) -> Vec<u8>
+    where
+        F: Fn(Option<T>) -> (u8, u8, u8),
+    {
+        let mut bytes = header("P6", bounds, scale);
+        bytes.extend_from_slice(b"255\n");
+        for row in self.pixel_rows(bounds, scale) {
+            for cell in row {
+                let (r, g, b) = color(cell);
+                bytes.extend_from_slice(&[r, g, b]);
+            }
+        }
+
+        bytes
+    }
+
+    fn to_pgm<F>(&self, bounds: Rect, scale: usize, grey: F) -> Vec<u8>
+    where
+        F: Fn(Option<T>) -> u8,
+    {
+        let mut bytes = header("P5", bounds, scale);
+        bytes.extend_from_slice(b"255\n");
+        for row in self.pixel_rows(bounds, scale) {
+            bytes.extend(row.into_iter().map(&grey));
+        }
+
+        bytes
+    }
+
+    fn to_pbm<F>(&self, bounds: Rect, scale: usize, is_black: F) -> Vec<u8>
+    where
+        F: Fn(Option<T>) -> bool,
+    {
+        let mut bytes = header("P4", bounds, scale);
+        for row in self.pixel_rows(bounds, scale) {
+            // eight pixels per byte, most significant bit first, every row
+            // padded to a whole byte
+            for chunk in row.chunks(8) {
+                let mut byte = 0;
+                for (i, cell) in chunk.iter().enumerate() {
+                    if is_black(*cell) {
+                        byte |= 0x80 >> i;
+                    }
+                }
+                bytes.push(byte);
+            }
+        }
+
+        bytes
+    }
+
+    // the cells behind every pixel of the scaled image, row by row
+    fn pixel_rows(&self, bounds: Rect, scale: usize) -> impl Iterator<Item = Vec<Option<T>>> + '_ {
+        assert!(scale > 0, "scale must be at least 1");
+
+        (bounds.min_y..=bounds.max_y).flat_map(move |y| {
+            let row: Vec<Option<T>> = (bounds.min_x..=bounds.max_x)
+                .flat_map(|x| std::iter::repeat_n(self.get(x, y), scale))
+                .collect();
+            std::iter::repeat_n(row, scale)
+        })
+    }
+}
+
+fn header(magic: &str, bounds: Rect, scale: usize) -> Vec<u8> {
+    format!(
+        "{}\n{} {}\n",
+        magic,
+        bounds.width() as usize * scale,
+        bounds.height() as usize * scale
+    )
+    .into_bytes()
+}
+
+// numbered ppm frames in one directory, e.g. to animate automaton steps with
+// ffmpeg -i frame_%05d.ppm out.gif
+pub struct FrameWriter {
+    dir: PathBuf,
+    scale: usize,
+    bounds: Option<Rect>,
+    count: usize,
+}
+
+impl FrameWriter {
+    pub fn new<P>(dir: P, scale: usize) -> io::Result<FrameWriter>
+    where
+        P: AsRef<Path>,
+    {
+        fs::create_dir_all(&dir)?;
+
+        Ok(FrameWriter {
+            dir: dir.as_ref().to_path_buf(),
+            scale,
+            bounds: None,
+            count: 0,
+        })
+    }
+
+    // draw every frame over the same area, by default the bounds of the first
+    // frame, so that growing grids don't change the image size
+    pub fn with_bounds(mut self, bounds: Rect) -> FrameWriter {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    // write the next frame, returning its path
+    pub fn write<T, F>(&mut self, grid: &Grid<T>, color: F) -> io::Result<PathBuf>
+    where
+        T: Copy,
+        F: Fn(Option<T>) -> (u8, u8, u8),
+    {
+        let bounds = *self.bounds.get_or_insert(grid.bounds);
+        let path = self.dir.join(format!("frame_{:05}.ppm", self.count));
+        fs::write(&path, grid.to_ppm(bounds, self.scale, color))?;
+        self.count += 1;
+
+        Ok(path)
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_grid() -> Grid<char> {
+        Grid::new(&["#.".to_string(), ".#".to_string(), "##".to_string()])
+    }
+
+    fn color(cell: Option<char>) -> (u8, u8, u8) {
+        match cell {
+            Some('#') => (255, 0, 0),
+            _ => (0, 0, 0),
+        }
+    }
+
+    #[test]
+    fn test_ppm() {
+        let grid = get_grid();
+        let bytes = grid.to_ppm(grid.bounds(), 1, color);
+
+        let header = b"P6\n2 3\n255\n";
+        assert_eq!(header, &bytes[..header.len()]);
+        assert_eq!(header.len() + 2 * 3 * 3, bytes.len());
+        assert_eq!(
+            &[255, 0, 0, 0, 0, 0],
+            &bytes[header.len()..header.len() + 6]
+        );
+    }
+
+    #[test]
+    fn test_pgm_scaled() {
+        let grid = get_grid();
+        let bytes = grid.to_pgm(grid.bounds(), 2, |c| if c == Some('#') { 0 } else { 255 });
+
+        let header = b"P5\n4 6\n255\n";
+        assert_eq!(header, &bytes[..header.len()]);
+        let pixels = &bytes[header.len()..];
+        assert_eq!(4 * 6, pixels.len());
+        assert_eq!(&[0, 0, 255, 255, 0, 0, 255, 255], &pixels[..8]);
+        assert_eq!(&[255, 255, 0, 0], &pixels[8..12]);
+    }
+
+    #[test]
+    fn test_pbm() {
+        let mut grid = get_grid();
+        grid.set('#', 8, 0);
+        let bytes = grid.to_pbm(grid.bounds(), 1, |c| c == Some('#'));
+
+        // 9 pixels wide, so every row takes two bytes
+        let header = b"P4\n9 3\n";
+        assert_eq!(header, &bytes[..header.len()]);
+        assert_eq!(
+            &[0b1000_0000, 0b1000_0000, 0b0100_0000, 0, 0b1100_0000, 0],
+            &bytes[header.len()..]
+        );
+    }
+
+    #[test]
+    fn test_frame_writer() {
+        let dir = std::env::temp_dir().join(format!("aocrust_frames_{}", std::process::id()));
+        let mut frames = FrameWriter::new(&dir, 1).unwrap();
+
+        let mut grid = get_grid();
+        frames.write(&grid, color).unwrap();
+        grid.set('#', 10, 10);
+        let path = frames.write(&grid, color).unwrap();
+
+        assert_eq!(2, frames.count());
+        assert_eq!(dir.join("frame_00001.ppm"), path);
+        let bytes = fs::read(&path).unwrap();
+        assert!(bytes.starts_with(b"P6\n2 3\n"));
+
+        grid.write_pbm(dir.join("grid.pbm"), 1, |c| c == Some('#'))
+            .unwrap();
+        assert!(fs::read(dir.join("grid.pbm"))
+            .unwrap()
+            .starts_with(b"P4\n11 11\n"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
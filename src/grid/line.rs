@@ -0,0 +1,170 @@
+use super::{Grid, Point};
+
+// the positions from start to end, both included, as drawn by Bresenham's
+// algorithm, so horizontal, vertical and 45° lines come out exact
+// https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+pub struct Line {
+    pos: Point,
+    end: Point,
+    dx: i64,
+    dy: i64,
+    step: Point,
+    err: i64,
+    done: bool,
+}
+
+impl Iterator for Line {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.done {
+            return None;
+        }
+
+        let cur = self.pos;
+        if cur == self.end {
+            self.done = true;
+        } else {
+            let e2 = 2 * self.err;
+            if e2 >= self.dy {
+                self.err += self.dy;
+                self.pos.x += self.step.x;
+            }
+            if e2 <= self.dx {
+                self.err += self.dx;
+                self.pos.y += self.step.y;
+            }
+        }
+
+        Some(cur)
+    }
+}
+
+pub fn line<A, B>(start: A, end: B) -> Line
+where
+    A: Into<Point>,
+    B: Into<Point>,
+{
+    let start = start.into();
+    let end = end.into();
+    let dx = (end.x - start.x).abs();
+    let dy = -(end.y - start.y).abs();
+
+    Line {
+        pos: start,
+        end,
+        dx,
+        dy,
+        step: Point::new((end.x - start.x).signum(), (end.y - start.y).signum()),
+        err: dx + dy,
+        done: false,
+    }
+}
+
+// like line, but only for horizontal, vertical and 45° lines, which is all
+// that most puzzles allow
+pub fn straight_line<A, B>(start: A, end: B) -> Option<Line>
+where
+    A: Into<Point>,
+    B: Into<Point>,
+{
+    let start = start.into();
+    let end = end.into();
+    let d = end - start;
+
+    if d.x == 0 || d.y == 0 || d.x.abs() == d.y.abs() {
+        Some(line(start, end))
+    } else {
+        None
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Copy,
+{
+    pub fn draw_line<A, B>(&mut self, start: A, end: B, value: T)
+    where
+        A: Into<Point>,
+        B: Into<Point>,
+    {
+        for p in line(start, end) {
+            self.set_point(value, p);
+        }
+    }
+
+    // lines between every pair of consecutive points, e.g. a path of rock
+    pub fn draw_path<P>(&mut self, points: &[P], value: T)
+    where
+        P: Into<Point> + Copy,
+    {
+        if let [p] = points {
+            self.set_point(value, *p);
+        }
+        for pair in points.windows(2) {
+            self.draw_line(pair[0], pair[1], value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_straight() {
+        let points: Vec<Point> = line((1, 1), (1, 3)).collect();
+        assert_eq!(
+            vec![Point::new(1, 1), Point::new(1, 2), Point::new(1, 3)],
+            points
+        );
+
+        let points: Vec<Point> = line((9, 7), (7, 9)).collect();
+        assert_eq!(
+            vec![Point::new(9, 7), Point::new(8, 8), Point::new(7, 9)],
+            points
+        );
+
+        assert_eq!(
+            vec![Point::new(2, 2)],
+            line((2, 2), (2, 2)).collect::<Vec<_>>()
+        );
+        assert_eq!(4, straight_line((3, 4), (0, 4)).unwrap().count());
+        assert!(straight_line((0, 0), (2, 1)).is_none());
+    }
+
+    #[test]
+    fn test_bresenham() {
+        let points: Vec<Point> = line((0, 0), (4, 2)).collect();
+        assert_eq!(
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(3, 2),
+                Point::new(4, 2)
+            ],
+            points
+        );
+
+        // always one point per step along the longer axis
+        assert_eq!(8, line((5, -3), (-2, 1)).count());
+        assert_eq!(Some(Point::new(-2, 1)), line((5, -3), (-2, 1)).last());
+    }
+
+    #[test]
+    fn test_draw() {
+        // regolith reservoir rock paths
+        let mut grid = Grid::empty();
+        grid.draw_path(&[(498, 4), (498, 6), (496, 6)], '#');
+        grid.draw_path(&[(503, 4), (502, 4), (502, 9), (494, 9)], '#');
+        assert_eq!(20, grid.data.len());
+
+        let mut grid = Grid::empty();
+        grid.draw_line((0, 0), (2, 2), 1);
+        grid.draw_line((2, 0), (0, 2), 1);
+        assert_eq!(5, grid.data.len());
+        assert_eq!(Some(1), grid.get(1, 1));
+        assert_eq!(None, grid.get(1, 0));
+    }
+}
@@ -1,104 +1,169 @@
+pub mod automaton;
+pub mod compress;
+pub mod cuboid;
+pub mod dense;
+pub mod dir;
+pub mod gravity;
 pub mod hex;
-
-use std::{
-    cmp::max,
-    cmp::min,
-    collections::{HashMap, HashSet},
-};
-
-pub static RIGHT: (i64, i64) = (1, 0);
-pub static LEFT: (i64, i64) = (-1, 0);
-pub static BOTTOM: (i64, i64) = (0, 1);
-pub static TOP: (i64, i64) = (0, -1);
-pub static TOP_LEFT: (i64, i64) = (-1, -1);
-pub static BOTTOM_RIGHT: (i64, i64) = (1, 1);
-pub static TOP_RIGHT: (i64, i64) = (1, -1);
-pub static BOTTOM_LEFT: (i64, i64) = (-1, 1);
+pub mod image;
+pub mod line;
+pub mod nd;
+pub mod ocr;
+pub mod point;
+mod ray;
+pub mod rect;
+pub mod region;
+mod transform;
+pub mod viz;
+pub mod wrap;
+
+pub use compress::{compress, Compression};
+pub use cuboid::{Cuboid, CuboidSet};
+pub use dense::DenseGrid;
+pub use dir::{Dir4, Dir8};
+pub use gravity::{settle_bricks, SettledBricks};
+pub use hex::{Hex, HexGrid};
+pub use image::FrameWriter;
+pub use line::{line, straight_line, Line};
+pub use nd::{Point3, Point4, SparseGridN};
+pub use point::Point;
+pub use rect::Rect;
+pub use region::{flood_fill, Region};
+pub use viz::Viz;
+pub use wrap::WrappingGrid;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
 
 lazy_static! {
-    pub static ref OFFSETS: Vec<(i64, i64)> = vec![
-        RIGHT,
-        LEFT,
-        BOTTOM,
-        TOP,
-        TOP_LEFT,
-        BOTTOM_RIGHT,
-        TOP_RIGHT,
-        BOTTOM_LEFT,
-    ];
-    pub static ref OFFSETS_STRAIGHT: Vec<(i64, i64)> = vec![RIGHT, LEFT, TOP, BOTTOM];
+    pub static ref OFFSETS: Vec<(i64, i64)> = [
+        Dir8::Right,
+        Dir8::Left,
+        Dir8::Down,
+        Dir8::Up,
+        Dir8::UpLeft,
+        Dir8::DownRight,
+        Dir8::UpRight,
+        Dir8::DownLeft,
+    ]
+    .iter()
+    .map(|d| d.offset().into())
+    .collect();
+    pub static ref OFFSETS_STRAIGHT: Vec<(i64, i64)> =
+        [Dir4::Right, Dir4::Left, Dir4::Up, Dir4::Down]
+            .iter()
+            .map(|d| d.offset().into())
+            .collect();
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Grid<T> {
     data: HashMap<(i64, i64), T>,
-    min_x: i64,
-    max_x: i64,
-    min_y: i64,
-    max_y: i64,
+    // smallest rectangle (inclusive) covering every cell that has been set
+    bounds: Rect,
 }
 
 impl<T> Grid<T> {
     pub fn empty() -> Grid<T> {
         Grid {
             data: HashMap::new(),
-            min_x: 0,
-            max_x: 0,
-            min_y: 0,
-            max_y: 0,
+            bounds: Rect::empty(),
         }
     }
 
-    pub fn new(input: &Vec<String>) -> Grid<char> {
-        let height = input.len();
-        let width = if height > 0 { input[0].len() } else { 0 };
-        let mut data = HashMap::new();
+    // one cell per character with (0, 0) in the top left corner, characters
+    // mapped to None are left unset
+    pub fn parse<F>(text: &str, f: F) -> Grid<T>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        Grid::parse_lines(text.lines(), |_, char| f(char))
+    }
+
+    // like parse, but the positions of the marker characters (e.g. S, E or @)
+    // are returned separately, in reading order as a marker may appear more
+    // than once, and the markers parsed as floor instead
+    pub fn parse_with_markers<F>(
+        text: &str,
+        markers: &[char],
+        floor: char,
+        f: F,
+    ) -> (Grid<T>, HashMap<char, Vec<Point>>)
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut positions: HashMap<char, Vec<Point>> = HashMap::new();
+        let grid = Grid::parse_lines(text.lines(), |p, char| {
+            if markers.contains(&char) {
+                positions.entry(char).or_default().push(p);
+                f(floor)
+            } else {
+                f(char)
+            }
+        });
+
+        (grid, positions)
+    }
+
+    // shared by parse, parse_with_markers and new
+    fn parse_lines<'a, I, F>(lines: I, mut f: F) -> Grid<T>
+    where
+        I: IntoIterator<Item = &'a str>,
+        F: FnMut(Point, char) -> Option<T>,
+    {
+        let mut grid = Grid::empty();
 
-        for (y, line) in input.iter().enumerate() {
+        for (y, line) in lines.into_iter().enumerate() {
             for (x, char) in line.chars().enumerate() {
-                data.insert((x as i64, y as i64), char);
+                if let Some(v) = f(Point::new(x as i64, y as i64), char) {
+                    grid.set(v, x as i64, y as i64);
+                }
             }
         }
 
-        Grid {
-            data,
-            min_x: 0,
-            max_x: width as i64,
-            min_y: 0,
-            max_y: height as i64,
-        }
+        grid
     }
 
     pub fn get(&self, x: i64, y: i64) -> Option<T>
     where
         T: Copy,
     {
-        match self.data.get(&(x, y)) {
-            Some(c) => Some(*c),
-            None => None,
-        }
+        self.data.get(&(x, y)).copied()
     }
 
     pub fn set(&mut self, input: T, x: i64, y: i64) {
         self.data.insert((x, y), input);
-        self.min_x = min(self.min_x, x);
-        self.max_x = max(self.max_x, x);
-        self.min_y = min(self.min_y, y);
-        self.max_y = max(self.max_y, y);
+        self.bounds.extend(x, y);
     }
 
-    fn prepare_print(&self) -> Vec<String>
+    pub fn get_point<P>(&self, p: P) -> Option<T>
     where
-        T: std::fmt::Display + Copy,
+        T: Copy,
+        P: Into<Point>,
+    {
+        let p = p.into();
+        self.get(p.x, p.y)
+    }
+
+    pub fn set_point<P>(&mut self, input: T, p: P)
+    where
+        P: Into<Point>,
+    {
+        let p = p.into();
+        self.set(input, p.x, p.y)
+    }
+
+    // one string per row of the bounding box, built from what f makes of
+    // each position and its cell
+    fn render_lines<F>(&self, mut f: F) -> Vec<String>
+    where
+        F: FnMut(Point, Option<&T>, &mut String),
     {
         let mut output = vec![];
-        for y in self.min_y..self.max_y {
+        for y in self.min_y()..=self.max_y() {
             let mut line = String::new();
-            for x in self.min_x..self.max_x {
-                match self.get(x, y) {
-                    Some(c) => line.push_str(&format!("{}", c)),
-                    _ => line.push('.'),
-                }
+            for x in self.min_x()..=self.max_x() {
+                f(Point::new(x, y), self.data.get(&(x, y)), &mut line);
             }
             output.push(line);
         }
@@ -106,13 +171,70 @@ impl<T> Grid<T> {
         output
     }
 
+    fn prepare_print_with_fill(&self, fill: char) -> Vec<String>
+    where
+        T: Display,
+    {
+        self.render_lines(|_, cell, line| match cell {
+            Some(c) => line.push_str(&c.to_string()),
+            None => line.push(fill),
+        })
+    }
+
+    fn prepare_print(&self) -> Vec<String>
+    where
+        T: Display,
+    {
+        self.prepare_print_with_fill('.')
+    }
+
+    // the bounding box with unset cells shown as fill, rows separated by
+    // newlines, to_string() is the same with '.' as fill
+    pub fn to_string_with_fill(&self, fill: char) -> String
+    where
+        T: Display,
+    {
+        self.prepare_print_with_fill(fill).join("\n")
+    }
+
+    // draw the bounding box one character per position, e.g. to overlay a path
+    pub fn render<F>(&self, f: F) -> String
+    where
+        F: Fn(Point, Option<&T>) -> char,
+    {
+        self.render_lines(|p, cell, line| line.push(f(p, cell)))
+            .join("\n")
+    }
+
+    // the grid as printed with to_string_with_fill, with marker drawn over the
+    // given points, such as a path found by dijkstra
+    pub fn overlay<I, P>(&self, points: I, marker: char, fill: char) -> String
+    where
+        T: Display,
+        I: IntoIterator<Item = P>,
+        P: Into<Point>,
+    {
+        let points: HashSet<Point> = points.into_iter().map(|p| p.into()).collect();
+        self.render_lines(|p, cell, line| match cell {
+            _ if points.contains(&p) => line.push(marker),
+            Some(c) => line.push_str(&c.to_string()),
+            None => line.push(fill),
+        })
+        .join("\n")
+    }
+
     pub fn print(&self)
     where
-        T: std::fmt::Display + Copy,
+        T: Display,
     {
-        for line in self.prepare_print() {
-            println!("{}", line);
-        }
+        self.print_with_fill('.');
+    }
+
+    pub fn print_with_fill(&self, fill: char)
+    where
+        T: Display,
+    {
+        println!("{}", self.to_string_with_fill(fill));
     }
 
     pub fn walk<F>(&mut self, mut visitor: F)
@@ -125,20 +247,39 @@ impl<T> Grid<T> {
         }
     }
 
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn width(&self) -> i64 {
+        self.bounds.width()
+    }
+
+    pub fn height(&self) -> i64 {
+        self.bounds.height()
+    }
+
+    // whether (x, y) lies within the bounding box, the cell itself may be unset
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        self.bounds.contains(x, y)
+    }
+
     pub fn min_x(&self) -> i64 {
-        self.min_x
+        self.bounds.min_x
     }
 
+    // inclusive, like all bounds
     pub fn max_x(&self) -> i64 {
-        self.max_x
+        self.bounds.max_x
     }
 
     pub fn min_y(&self) -> i64 {
-        self.min_y
+        self.bounds.min_y
     }
 
+    // inclusive, like all bounds
     pub fn max_y(&self) -> i64 {
-        self.max_y
+        self.bounds.max_y
     }
 
     pub fn to_graph(&self, values: HashSet<T>) -> HashMap<(i64, i64), Vec<(i64, i64)>>
@@ -171,42 +312,59 @@ impl<T> Grid<T> {
     }
 }
 
+impl Grid<char> {
+    pub fn new(input: &[String]) -> Grid<char> {
+        Grid::parse_lines(input.iter().map(|l| l.as_str()), |_, char| Some(char))
+    }
+}
+
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.prepare_print().join("\n"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn get_input<'a>() -> Vec<String> {
-        let mut data: Vec<String> = vec![];
-        data.push(".......#................#......".to_string());
-        data.push("...#.#.....#.##.....#..#.......".to_string());
-        data.push("..#..#.#......#.#.#............".to_string());
-        data.push("....#...#...##.....#..#.....#..".to_string());
-        data.push("....#.......#.##......#...#..#.".to_string());
-        data.push("...............#.#.#.....#..#..".to_string());
-        data.push("...##...#...#..##.###...##.....".to_string());
-        data.push("##..#.#...##.....#.#..........#".to_string());
-        data.push(".#....#..#..#......#....#....#.".to_string());
-
-        data
+    use crate::graph::dijkstra::dijkstra;
+
+    fn get_input() -> Vec<String> {
+        vec![
+            ".......#................#......".to_string(),
+            "...#.#.....#.##.....#..#.......".to_string(),
+            "..#..#.#......#.#.#............".to_string(),
+            "....#...#...##.....#..#.....#..".to_string(),
+            "....#.......#.##......#...#..#.".to_string(),
+            "...............#.#.#.....#..#..".to_string(),
+            "...##...#...#..##.###...##.....".to_string(),
+            "##..#.#...##.....#.#..........#".to_string(),
+            ".#....#..#..#......#....#....#.".to_string(),
+        ]
     }
 
     #[test]
     fn test_empty() {
         let grid: Grid<char> = Grid::empty();
-        assert_eq!(0, grid.min_x);
-        assert_eq!(0, grid.max_x);
-        assert_eq!(0, grid.min_y);
-        assert_eq!(0, grid.max_y);
+        assert!(grid.bounds().is_empty());
+        assert_eq!(0, grid.width());
+        assert_eq!(0, grid.height());
+        assert!(!grid.contains(0, 0));
+        assert_eq!(0, grid.prepare_print().len());
     }
 
     #[test]
     fn test_new() {
-        let grid: Grid<char> = Grid::<char>::new(&get_input());
+        let grid: Grid<char> = Grid::new(&get_input());
 
-        assert_eq!(0, grid.min_x);
-        assert_eq!(31, grid.max_x);
-        assert_eq!(0, grid.min_y);
-        assert_eq!(9, grid.max_y);
+        assert_eq!(Rect::new(0, 0, 30, 8), grid.bounds());
+        assert_eq!(31, grid.width());
+        assert_eq!(9, grid.height());
+        assert!(grid.contains(30, 8));
+        assert!(!grid.contains(31, 8));
     }
 
     #[test]
@@ -214,25 +372,92 @@ mod tests {
         let mut grid = Grid::empty();
         grid.set('#', -10, 23);
         grid.set('#', 100, -2);
-        assert_eq!(-10, grid.min_x);
-        assert_eq!(100, grid.max_x);
-        assert_eq!(-2, grid.min_y);
-        assert_eq!(23, grid.max_y);
+        assert_eq!(-10, grid.min_x());
+        assert_eq!(100, grid.max_x());
+        assert_eq!(-2, grid.min_y());
+        assert_eq!(23, grid.max_y());
+        assert_eq!(111, grid.width());
+        assert_eq!(26, grid.height());
+    }
+
+    #[test]
+    fn test_set_negative() {
+        let mut grid = Grid::empty();
+        grid.set('#', -5, -3);
+        grid.set('#', -2, -4);
+        assert_eq!(Rect::new(-5, -4, -2, -3), grid.bounds());
+        assert_eq!(vec!["...#", "#..."], grid.prepare_print());
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("12.\n.93\n", |c| c.to_digit(10));
+
+        assert_eq!(Rect::new(0, 0, 2, 1), grid.bounds());
+        assert_eq!(Some(1), grid.get(0, 0));
+        assert_eq!(Some(9), grid.get(1, 1));
+        assert_eq!(None, grid.get(2, 0));
+
+        let walls = Grid::parse("#.\n.#", |c| Some(c == '#'));
+        assert_eq!(Some(false), walls.get(1, 0));
+        assert_eq!(Some(true), walls.get(1, 1));
+    }
+
+    #[test]
+    fn test_parse_with_markers() {
+        let (grid, markers) =
+            Grid::parse_with_markers("#S.\n..E", &['S', 'E', '@'], '.', |c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            });
+
+        assert_eq!(6, grid.width() * grid.height());
+        assert_eq!(Some(false), grid.get(1, 0));
+        assert_eq!(Some(false), grid.get(2, 1));
+        assert_eq!(Some(&vec![Point::new(1, 0)]), markers.get(&'S'));
+        assert_eq!(Some(&vec![Point::new(2, 1)]), markers.get(&'E'));
+        assert_eq!(None, markers.get(&'@'));
+
+        // every copy of a repeated marker is kept
+        let (grid, markers) = Grid::parse_with_markers(
+            "@#@
+.@.",
+            &['@'],
+            '.',
+            Some,
+        );
+        assert_eq!(Some('.'), grid.get(2, 0));
+        assert_eq!(
+            Some(&vec![Point::new(0, 0), Point::new(2, 0), Point::new(1, 1)]),
+            markers.get(&'@')
+        );
     }
 
     #[test]
     fn test_get() {
-        let grid: Grid<char> = Grid::<char>::new(&get_input());
+        let grid: Grid<char> = Grid::new(&get_input());
 
         assert_eq!(Some('.'), grid.get(0, 0));
         assert_eq!(Some('#'), grid.get(3, 1));
         assert_eq!(None, grid.get(-3000, -3000));
+        assert_eq!(Some('#'), grid.get_point(Point::new(3, 1)));
+        assert_eq!(Some('#'), grid.get_point((3, 1)));
+    }
+
+    #[test]
+    fn test_set_point() {
+        let mut grid = Grid::empty();
+        grid.set_point(1, Point::new(-1, 2));
+        grid.set_point(2, (3, 4));
+        assert_eq!(Some(1), grid.get(-1, 2));
+        assert_eq!(Some(2), grid.get_point(Point::new(3, 4)));
     }
 
     #[test]
     fn test_print() {
         let input = get_input();
-        let grid = Grid::<char>::new(&input);
+        let grid = Grid::new(&input);
 
         let output = grid.prepare_print();
 
@@ -244,6 +469,52 @@ mod tests {
         grid.print();
     }
 
+    #[test]
+    fn test_display() {
+        let mut grid = Grid::empty();
+        grid.set(1, 0, 0);
+        grid.set(23, 2, 1);
+
+        assert_eq!("1..\n..23", grid.to_string());
+        assert_eq!("1  \n  23", grid.to_string_with_fill(' '));
+        assert_eq!("", Grid::<char>::empty().to_string());
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::new(&["#..".to_string(), "..#".to_string()]);
+
+        let rendered = grid.render(|p, c| match c {
+            Some('#') => '█',
+            _ if p.x == p.y => '\\',
+            _ => ' ',
+        });
+        assert_eq!("█  \n \\█", rendered);
+    }
+
+    #[test]
+    fn test_overlay() {
+        let grid = Grid::new(&["...".to_string(), ".#.".to_string(), "...".to_string()]);
+        let graph = grid.to_graph(HashSet::from(['.']));
+        let (path, _dist, _prev) = dijkstra(&(0, 1), &(2, 1), &graph);
+
+        let overlay = grid.overlay(path.into_iter().copied(), 'O', '.');
+        assert_eq!(5, overlay.matches('O').count());
+        assert_eq!(3, overlay.lines().count());
+        assert_eq!('#', overlay.lines().nth(1).unwrap().chars().nth(1).unwrap());
+
+        // unset cells use the same fill as plain rendering
+        let mut sparse = Grid::empty();
+        sparse.set(1, 0, 0);
+        sparse.set(2, 2, 1);
+        assert_eq!("1  \n  X", sparse.overlay([(2, 1)], 'X', ' '));
+        assert_eq!(
+            sparse.to_string_with_fill(' '),
+            sparse.overlay(Vec::<Point>::new(), 'X', ' ')
+        );
+        sparse.print_with_fill(' ');
+    }
+
     #[test]
     fn test_count() {
         let mut grid = Grid::empty();
@@ -264,7 +535,7 @@ mod tests {
 
     #[test]
     fn test_to_graph() {
-        let grid: Grid<char> = Grid::<char>::new(&get_input());
+        let grid: Grid<char> = Grid::new(&get_input());
         let mut values = HashSet::new();
         values.insert('.');
 
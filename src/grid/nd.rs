@@ -0,0 +1,271 @@
+use std::cmp::{max, min};
+use std::collections::{BTreeSet, HashMap};
+
+// Positions in D dimensions are plain arrays, x first.
+pub type Point3 = [i64; 3];
+pub type Point4 = [i64; 4];
+
+// all 3^D - 1 surrounding positions, e.g. 8 in 2D, 26 in 3D, 80 in 4D
+pub fn moore<const D: usize>(p: [i64; D]) -> impl Iterator<Item = [i64; D]> {
+    (0..3_i64.pow(D as u32)).filter_map(move |mut i| {
+        let mut n = p;
+        let mut zero = true;
+        for c in n.iter_mut() {
+            let d = i % 3 - 1;
+            *c += d;
+            zero &= d == 0;
+            i /= 3;
+        }
+
+        if zero {
+            None
+        } else {
+            Some(n)
+        }
+    })
+}
+
+// the 2 * D positions sharing a face, e.g. 4 in 2D and 6 in 3D
+pub fn von_neumann<const D: usize>(p: [i64; D]) -> impl Iterator<Item = [i64; D]> {
+    (0..2 * D).map(move |i| {
+        let mut n = p;
+        n[i / 2] += if i % 2 == 0 { 1 } else { -1 };
+        n
+    })
+}
+
+// the D dimensional counterpart of Grid, storing only the cells that are set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGridN<const D: usize, T> {
+    data: HashMap<[i64; D], T>,
+    min: [i64; D],
+    max: [i64; D],
+}
+
+impl<const D: usize, T> SparseGridN<D, T> {
+    pub fn empty() -> SparseGridN<D, T> {
+        SparseGridN {
+            data: HashMap::new(),
+            min: [0; D],
+            max: [0; D],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, p: [i64; D]) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.data.get(&p).copied()
+    }
+
+    pub fn set(&mut self, input: T, p: [i64; D]) {
+        if self.data.is_empty() {
+            self.min = p;
+            self.max = p;
+        } else {
+            for (i, c) in p.iter().enumerate() {
+                self.min[i] = min(self.min[i], *c);
+                self.max[i] = max(self.max[i], *c);
+            }
+        }
+        self.data.insert(p, input);
+    }
+
+    // inclusive (min, max) corners of the box covering every cell
+    pub fn bounds(&self) -> Option<([i64; D], [i64; D])> {
+        if self.data.is_empty() {
+            None
+        } else {
+            Some((self.min, self.max))
+        }
+    }
+
+    pub fn contains(&self, p: [i64; D]) -> bool {
+        !self.data.is_empty() && (0..D).all(|i| p[i] >= self.min[i] && p[i] <= self.max[i])
+    }
+
+    pub fn walk<F>(&self, mut visitor: F)
+    where
+        T: Copy,
+        F: FnMut([i64; D], T),
+    {
+        for (k, v) in &self.data {
+            visitor(*k, *v)
+        }
+    }
+
+    // the set cells among the 2 * D face neighbours of p
+    pub fn face_neighbours(&self, p: [i64; D]) -> impl Iterator<Item = ([i64; D], T)> + '_
+    where
+        T: Copy,
+    {
+        von_neumann(p).filter_map(move |n| self.get(n).map(|v| (n, v)))
+    }
+
+    // the set cells among all 3^D - 1 neighbours of p
+    pub fn neighbours(&self, p: [i64; D]) -> impl Iterator<Item = ([i64; D], T)> + '_
+    where
+        T: Copy,
+    {
+        moore(p).filter_map(move |n| self.get(n).map(|v| (n, v)))
+    }
+
+    // one x/y layer per combination of the remaining coordinates that has
+    // any cells, each headed like "z=0, w=1" and covering the full x/y bounds,
+    // grids with fewer than two dimensions print as a single row
+    fn prepare_print(&self) -> Vec<String>
+    where
+        T: std::fmt::Display + Copy,
+    {
+        let layers: BTreeSet<Vec<i64>> = self
+            .data
+            .keys()
+            .map(|p| p.get(2..).unwrap_or(&[]).to_vec())
+            .collect();
+        let names = ["z", "w"];
+        let range = |i: usize| match (self.min.get(i), self.max.get(i)) {
+            (Some(lo), Some(hi)) => *lo..=*hi,
+            _ => 0..=0,
+        };
+
+        let mut output = vec![];
+        for layer in layers {
+            if !layer.is_empty() {
+                let header: Vec<String> = layer
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| format!("{}={}", names.get(i).unwrap_or(&"?"), v))
+                    .collect();
+                output.push(header.join(", "));
+            }
+
+            for y in range(1) {
+                let mut line = String::new();
+                for x in range(0) {
+                    let mut p = self.min;
+                    if let Some(c) = p.get_mut(0) {
+                        *c = x;
+                    }
+                    if let Some(c) = p.get_mut(1) {
+                        *c = y;
+                    }
+                    if let Some(rest) = p.get_mut(2..) {
+                        rest.copy_from_slice(&layer);
+                    }
+                    match self.get(p) {
+                        Some(c) => line.push_str(&format!("{}", c)),
+                        _ => line.push('.'),
+                    }
+                }
+                output.push(line);
+            }
+            output.push(String::new());
+        }
+        output.pop();
+
+        output
+    }
+
+    pub fn print(&self)
+    where
+        T: std::fmt::Display + Copy,
+    {
+        for line in self.prepare_print() {
+            println!("{}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbourhoods() {
+        assert_eq!(8, moore([0, 0]).count());
+        assert_eq!(26, moore([0, 0, 0]).count());
+        assert_eq!(80, moore([1, 2, 3, 4]).count());
+        assert!(!moore([1, 2, 3]).any(|n| n == [1, 2, 3]));
+        assert_eq!(6, von_neumann([0, 0, 0]).count());
+        assert_eq!(8, von_neumann([0, 0, 0, 0]).count());
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid: SparseGridN<3, char> = SparseGridN::empty();
+        assert_eq!(None, grid.bounds());
+        assert!(!grid.contains([0, 0, 0]));
+
+        grid.set('#', [1, -2, 3]);
+        grid.set('#', [-1, 4, 3]);
+        assert_eq!(2, grid.len());
+        assert_eq!(Some('#'), grid.get([1, -2, 3]));
+        assert_eq!(None, grid.get([0, 0, 0]));
+        assert_eq!(Some(([-1, -2, 3], [1, 4, 3])), grid.bounds());
+        assert!(grid.contains([0, 0, 3]));
+        assert!(!grid.contains([0, 0, 2]));
+    }
+
+    #[test]
+    fn test_surface_area() {
+        // lava droplet example, two touching cubes expose 10 faces
+        let mut grid: SparseGridN<3, bool> = SparseGridN::empty();
+        grid.set(true, [1, 1, 1]);
+        grid.set(true, [2, 1, 1]);
+
+        let mut exposed = 0;
+        grid.walk(|p, _| exposed += 6 - grid.face_neighbours(p).count());
+        assert_eq!(10, exposed);
+        assert_eq!(1, grid.neighbours([1, 1, 1]).count());
+    }
+
+    #[test]
+    fn test_print() {
+        let mut grid: SparseGridN<4, char> = SparseGridN::empty();
+        grid.set('#', [0, 0, 0, 0]);
+        grid.set('#', [1, 1, 0, 1]);
+        grid.set('#', [0, 1, -1, 1]);
+
+        assert_eq!(
+            vec![
+                "z=-1, w=1",
+                "..",
+                "#.",
+                "",
+                "z=0, w=0",
+                "#.",
+                "..",
+                "",
+                "z=0, w=1",
+                "..",
+                ".#"
+            ],
+            grid.prepare_print()
+        );
+        grid.print();
+
+        let mut grid: SparseGridN<2, char> = SparseGridN::empty();
+        grid.set('#', [1, 1]);
+        assert_eq!(vec!["#"], grid.prepare_print());
+    }
+
+    #[test]
+    fn test_print_1d() {
+        let mut grid: SparseGridN<1, char> = SparseGridN::empty();
+        grid.set('#', [2]);
+        assert_eq!(vec!["#"], grid.prepare_print());
+
+        grid.set('#', [-1]);
+        assert_eq!(vec!["#..#"], grid.prepare_print());
+        assert_eq!(1, grid.neighbours([0]).count());
+        grid.print();
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{Grid, Point};
+
+// The letters drawn by puzzles, 4 wide and 6 high or 6 wide and 10 high. Blank
+// columns are trimmed before matching, so narrow letters like I line up no
+// matter where they were drawn.
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.|#..#|#..#|####|#..#|#..#"),
+    ('B', "###.|#..#|###.|#..#|#..#|###."),
+    ('C', ".##.|#..#|#...|#...|#..#|.##."),
+    ('E', "####|#...|###.|#...|#...|####"),
+    ('F', "####|#...|###.|#...|#...|#..."),
+    ('G', ".##.|#..#|#...|#.##|#..#|.###"),
+    ('H', "#..#|#..#|####|#..#|#..#|#..#"),
+    ('I', ".###|..#.|..#.|..#.|..#.|.###"),
+    ('J', "..##|...#|...#|...#|#..#|.##."),
+    ('K', "#..#|#.#.|##..|#.#.|#.#.|#..#"),
+    ('L', "#...|#...|#...|#...|#...|####"),
+    ('O', ".##.|#..#|#..#|#..#|#..#|.##."),
+    ('P', "###.|#..#|#..#|###.|#...|#..."),
+    ('R', "###.|#..#|#..#|###.|#.#.|#..#"),
+    ('S', ".###|#...|#...|.##.|...#|###."),
+    ('U', "#..#|#..#|#..#|#..#|#..#|.##."),
+    ('Y', "#...#|#...#|.#.#.|..#..|..#..|..#.."),
+    ('Z', "####|...#|..#.|.#..|#...|####"),
+];
+
+const LARGE: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..|.#..#.|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'B',
+        "#####.|#....#|#....#|#....#|#####.|#....#|#....#|#....#|#....#|#####.",
+    ),
+    (
+        'C',
+        ".####.|#....#|#.....|#.....|#.....|#.....|#.....|#.....|#....#|.####.",
+    ),
+    (
+        'E',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'F',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'G',
+        ".####.|#....#|#.....|#.....|#.....|#..###|#....#|#....#|#...##|.###.#",
+    ),
+    (
+        'H',
+        "#....#|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'J',
+        "...###|....#.|....#.|....#.|....#.|....#.|....#.|#...#.|#...#.|.###..",
+    ),
+    (
+        'K',
+        "#....#|#...#.|#..#..|#.#...|##....|##....|#.#...|#..#..|#...#.|#....#",
+    ),
+    (
+        'L',
+        "#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'N',
+        "#....#|##...#|##...#|#.#..#|#.#..#|#..#.#|#..#.#|#...##|#...##|#....#",
+    ),
+    (
+        'P',
+        "#####.|#....#|#....#|#....#|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'R',
+        "#####.|#....#|#....#|#....#|#####.|#..#..|#...#.|#...#.|#....#|#....#",
+    ),
+    (
+        'X',
+        "#....#|#....#|.#..#.|.#..#.|..##..|..##..|.#..#.|.#..#.|#....#|#....#",
+    ),
+    (
+        'Z',
+        "######|.....#|.....#|....#.|...#..|..#...|.#....|#.....|#.....|######",
+    ),
+];
+
+lazy_static! {
+    static ref GLYPHS: HashMap<String, char> = SMALL
+        .iter()
+        .chain(LARGE.iter())
+        .map(|(letter, art)| {
+            let rows: Vec<&str> = art.split('|').collect();
+            let points: Vec<Point> = rows
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.chars()
+                        .enumerate()
+                        .filter(|(_, c)| *c == '#')
+                        .map(move |(x, _)| Point::new(x as i64, y as i64))
+                })
+                .collect();
+            (glyph_key(&points, rows.len() as i64), *letter)
+        })
+        .collect();
+}
+
+// the glyph drawn by the points as rows joined by '|', trimmed to the lit
+// columns but keeping all rows from 0 to height so glyphs sharing a line
+// compare the same way
+fn glyph_key(points: &[Point], height: i64) -> String {
+    let min_x = points.iter().map(|p| p.x).min().unwrap_or(0);
+    let max_x = points.iter().map(|p| p.x).max().unwrap_or(-1);
+    let lit: HashSet<&Point> = points.iter().collect();
+
+    let rows: Vec<String> = (0..height)
+        .map(|y| {
+            (min_x..=max_x)
+                .map(|x| {
+                    if lit.contains(&Point::new(x, y)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    rows.join("|")
+}
+
+// the letters drawn with '#' in the grid
+pub fn read_letters(grid: &Grid<char>) -> Result<String, String> {
+    read_points(
+        grid.data
+            .iter()
+            .filter(|(_, c)| **c == '#')
+            .map(|(p, _)| Point::from(*p)),
+    )
+}
+
+// the letters drawn by the lit points, or an error listing the columns and
+// shape of every glyph that isn't a known letter
+pub fn read_points<I, P>(points: I) -> Result<String, String>
+where
+    I: IntoIterator<Item = P>,
+    P: Into<Point>,
+{
+    let points: HashSet<Point> = points.into_iter().map(|p| p.into()).collect();
+    if points.is_empty() {
+        return Ok(String::new());
+    }
+
+    let min_y = points.iter().map(|p| p.y).min().unwrap();
+    let max_y = points.iter().map(|p| p.y).max().unwrap();
+    let mut columns: Vec<i64> = points.iter().map(|p| p.x).collect();
+    columns.sort_unstable();
+    columns.dedup();
+
+    // glyphs are separated by at least one blank column
+    let mut glyphs: Vec<(i64, i64)> = vec![];
+    for x in columns {
+        match glyphs.last_mut() {
+            Some((_, end)) if *end + 1 == x => *end = x,
+            _ => glyphs.push((x, x)),
+        }
+    }
+
+    let mut letters = String::new();
+    let mut unknown = vec![];
+    for (start, end) in glyphs {
+        let glyph: Vec<Point> = points
+            .iter()
+            .filter(|p| p.x >= start && p.x <= end)
+            .map(|p| Point::new(p.x, p.y - min_y))
+            .collect();
+        let key = glyph_key(&glyph, max_y - min_y + 1);
+
+        match GLYPHS.get(&key) {
+            Some(letter) => letters.push(*letter),
+            None => unknown.push(format!(
+                "columns {}..={}:\n{}",
+                start,
+                end,
+                key.replace('|', "\n")
+            )),
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(letters)
+    } else {
+        Err(format!("unrecognised glyphs\n{}", unknown.join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(rows: &[&str]) -> Grid<char> {
+        Grid::new(&rows.iter().map(|r| r.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_read_small() {
+        let grid = draw(&[
+            ".##..###..####.#..#.",
+            "#..#.#..#.#....#..#.",
+            "#..#.###..###..####.",
+            "####.#..#.#....#..#.",
+            "#..#.#..#.#....#..#.",
+            "#..#.###..####.#..#.",
+        ]);
+        assert_eq!(Ok("ABEH".to_string()), read_letters(&grid));
+    }
+
+    #[test]
+    fn test_read_large() {
+        let mut points = vec![];
+        for (i, letter) in "HXN".chars().enumerate() {
+            let (_, art) = LARGE.iter().find(|(l, _)| *l == letter).unwrap();
+            for (y, row) in art.split('|').enumerate() {
+                for (x, c) in row.chars().enumerate() {
+                    if c == '#' {
+                        points.push((100 + 8 * i as i64 + x as i64, y as i64 - 5));
+                    }
+                }
+            }
+        }
+        assert_eq!(Ok("HXN".to_string()), read_points(points));
+    }
+
+    #[test]
+    fn test_narrow_letters() {
+        // I has a blank leading column in the font
+        let grid = draw(&[
+            ".###.#...",
+            "..#..#...",
+            "..#..#...",
+            "..#..#...",
+            "..#..#...",
+            ".###.####",
+        ]);
+        assert_eq!(Ok("IL".to_string()), read_letters(&grid));
+    }
+
+    #[test]
+    fn test_unrecognised() {
+        let grid = draw(&[
+            "#..#..#.", "#..#....", "####..#.", "#..#....", "#..#..#.", "#..#..#.",
+        ]);
+        let err = read_letters(&grid).unwrap_err();
+        assert!(err.contains("columns 6..=6"));
+        assert!(!err.contains("columns 0..=3"));
+        assert_eq!(Ok(String::new()), read_points(Vec::<Point>::new()));
+    }
+}
@@ -0,0 +1,175 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use super::{OFFSETS, OFFSETS_STRAIGHT};
+use crate::math::{gcd, manhattan_dist};
+
+// a position or offset on the grid, with y growing downwards like the rows of
+// the input
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        manhattan_dist(self.x, self.y, other.x, other.y)
+    }
+
+    pub fn chebyshev(self, other: Point) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    // straight neighbours, in the same order as OFFSETS_STRAIGHT
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        OFFSETS_STRAIGHT.iter().map(move |o| self + Point::from(*o))
+    }
+
+    // straight and diagonal neighbours, in the same order as OFFSETS
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        OFFSETS.iter().map(move |o| self + Point::from(*o))
+    }
+
+    // rotate 90° clockwise around the origin as seen on screen, so right turns
+    // into down
+    pub fn rotate_cw(self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    pub fn rotate_ccw(self) -> Point {
+        Point::new(self.y, -self.x)
+    }
+
+    // the smallest step in the same direction, e.g. (4, -6) reduces to (2, -3)
+    pub fn reduced(self) -> Point {
+        match gcd(self.x, self.y) {
+            0 => self,
+            d => Point::new(self.x / d, self.y / d),
+        }
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from(p: (i64, i64)) -> Point {
+        Point::new(p.0, p.1)
+    }
+}
+
+impl From<Point> for (i64, i64) {
+    fn from(p: Point) -> (i64, i64) {
+        (p.x, p.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let mut p = Point::new(1, 2);
+        assert_eq!(Point::new(4, 0), p + Point::new(3, -2));
+        assert_eq!(Point::new(-2, 4), p - Point::new(3, -2));
+        assert_eq!(Point::new(3, 6), p * 3);
+        assert_eq!(Point::new(-1, -2), -p);
+
+        p += Point::new(1, 1);
+        p -= Point::new(0, 2);
+        assert_eq!(Point::new(2, 1), p);
+    }
+
+    #[test]
+    fn test_conversion() {
+        let p: Point = (3, -4).into();
+        assert_eq!(Point::new(3, -4), p);
+
+        let t: (i64, i64) = p.into();
+        assert_eq!((3, -4), t);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(-5, -5);
+        let b = Point::new(5, 2);
+        assert_eq!(17, a.manhattan(b));
+        assert_eq!(10, a.chebyshev(b));
+        assert_eq!(0, a.chebyshev(a));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point::new(10, 10);
+        let n4: Vec<Point> = p.neighbours4().collect();
+        assert_eq!(4, n4.len());
+        assert!(n4.iter().all(|n| n.manhattan(p) == 1));
+        assert_eq!(Point::new(11, 10), n4[0]);
+
+        let n8: Vec<Point> = p.neighbours8().collect();
+        assert_eq!(8, n8.len());
+        assert!(n8.iter().all(|n| n.chebyshev(p) == 1));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let right = Point::new(1, 0);
+        assert_eq!(Point::new(0, 1), right.rotate_cw());
+        assert_eq!(Point::new(0, -1), right.rotate_ccw());
+        assert_eq!(-right, right.rotate_cw().rotate_cw());
+        assert_eq!(right, right.rotate_cw().rotate_ccw());
+    }
+
+    #[test]
+    fn test_reduced() {
+        assert_eq!(Point::new(2, -3), Point::new(4, -6).reduced());
+        assert_eq!(Point::new(0, 1), Point::new(0, 7).reduced());
+        assert_eq!(Point::new(0, 0), Point::new(0, 0).reduced());
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use super::{Grid, Point};
+
+impl<T> Grid<T> {
+    // positions from (but not including) from, stepping by dir until leaving
+    // the bounding box
+    pub fn ray<P, D>(&self, from: P, dir: D) -> impl Iterator<Item = Point> + '_
+    where
+        P: Into<Point>,
+        D: Into<Point>,
+    {
+        let dir = dir.into();
+        assert!(dir != Point::new(0, 0), "ray needs a direction");
+
+        let mut pos = from.into();
+        std::iter::from_fn(move || {
+            pos += dir;
+            if self.contains(pos.x, pos.y) {
+                Some(pos)
+            } else {
+                None
+            }
+        })
+    }
+
+    // the first cell along the ray that is_blocking accepts, unset cells never
+    // block
+    pub fn first_visible<P, D, F>(&self, from: P, dir: D, is_blocking: F) -> Option<(Point, T)>
+    where
+        T: Copy,
+        P: Into<Point>,
+        D: Into<Point>,
+        F: Fn(T) -> bool,
+    {
+        self.ray(from, dir)
+            .filter_map(|p| self.get_point(p).map(|v| (p, v)))
+            .find(|(_, v)| is_blocking(*v))
+    }
+
+    // every cell accepted by is_target that isn't hidden behind another one in
+    // the same direction, at any rational angle, ordered clockwise starting
+    // straight up
+    pub fn visible_from<P, F>(&self, from: P, is_target: F) -> Vec<Point>
+    where
+        T: Copy,
+        P: Into<Point>,
+        F: Fn(T) -> bool,
+    {
+        let from = from.into();
+        let mut nearest: HashMap<Point, Point> = HashMap::new();
+
+        for (pos, value) in self.data.iter() {
+            let pos = Point::from(*pos);
+            if pos == from || !is_target(*value) {
+                continue;
+            }
+
+            let dir = (pos - from).reduced();
+            match nearest.get(&dir) {
+                Some(n) if n.manhattan(from) <= pos.manhattan(from) => {}
+                _ => {
+                    nearest.insert(dir, pos);
+                }
+            }
+        }
+
+        let mut visible: Vec<(f64, Point)> = nearest
+            .into_iter()
+            .map(|(dir, pos)| (clockwise_angle(dir), pos))
+            .collect();
+        visible.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        visible.into_iter().map(|(_, pos)| pos).collect()
+    }
+}
+
+// angle from straight up (y - 1), growing clockwise on screen, in [0, 2π)
+fn clockwise_angle(dir: Point) -> f64 {
+    let angle = (dir.x as f64).atan2(-dir.y as f64);
+    if angle < 0.0 {
+        angle + 2.0 * PI
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Dir4, Dir8};
+
+    fn get_grid() -> Grid<char> {
+        Grid::new(&[
+            ".#..#".to_string(),
+            ".....".to_string(),
+            "#####".to_string(),
+            "....#".to_string(),
+            "...##".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = get_grid();
+
+        let ray: Vec<Point> = grid.ray((1, 1), Dir4::Right.offset()).collect();
+        assert_eq!(
+            vec![Point::new(2, 1), Point::new(3, 1), Point::new(4, 1)],
+            ray
+        );
+        assert_eq!(0, grid.ray((0, 0), Dir8::UpLeft.offset()).count());
+        assert_eq!(2, grid.ray((0, 0), (2, 1)).count());
+    }
+
+    #[test]
+    fn test_first_visible() {
+        let grid = get_grid();
+
+        assert_eq!(
+            Some((Point::new(1, 2), '#')),
+            grid.first_visible((1, 0), Dir4::Down.offset(), |c| c == '#')
+        );
+        assert_eq!(
+            None,
+            grid.first_visible((0, 1), Dir4::Right.offset(), |c| c == '#')
+        );
+        assert_eq!(
+            Some((Point::new(2, 2), '#')),
+            grid.first_visible((0, 0), (1, 1), |c| c == '#')
+        );
+    }
+
+    #[test]
+    fn test_visible_from() {
+        // asteroid example, the best location at (3, 4) sees 8 asteroids
+        let grid = get_grid();
+
+        let visible = grid.visible_from((3, 4), |c| c == '#');
+        assert_eq!(8, visible.len());
+        assert!(!visible.contains(&Point::new(1, 0)));
+        assert_eq!(Point::new(3, 2), visible[0]);
+        assert_eq!(Point::new(4, 0), visible[1]);
+        assert_eq!(Point::new(4, 2), visible[2]);
+        assert_eq!(7, grid.visible_from((1, 0), |c| c == '#').len());
+    }
+}
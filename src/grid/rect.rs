@@ -0,0 +1,160 @@
+use std::cmp::{max, min};
+
+// an axis aligned rectangle with inclusive bounds, a rectangle with max below
+// min on either axis is empty
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min_x: i64,
+    pub min_y: i64,
+    pub max_x: i64,
+    pub max_y: i64,
+}
+
+impl Rect {
+    pub fn new(min_x: i64, min_y: i64, max_x: i64, max_y: i64) -> Rect {
+        Rect {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        }
+    }
+
+    pub fn empty() -> Rect {
+        Rect::new(0, 0, -1, -1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max_x < self.min_x || self.max_y < self.min_y
+    }
+
+    pub fn width(&self) -> i64 {
+        max(0, self.max_x - self.min_x + 1)
+    }
+
+    pub fn height(&self) -> i64 {
+        max(0, self.max_y - self.min_y + 1)
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        x >= self.min_x && x <= self.max_x && y >= self.min_y && y <= self.max_y
+    }
+
+    // grow the rectangle just enough to cover (x, y)
+    pub fn extend(&mut self, x: i64, y: i64) {
+        if self.is_empty() {
+            *self = Rect::new(x, y, x, y);
+        } else {
+            self.min_x = min(self.min_x, x);
+            self.max_x = max(self.max_x, x);
+            self.min_y = min(self.min_y, y);
+            self.max_y = max(self.max_y, y);
+        }
+    }
+
+    // number of positions covered
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let r = Rect::new(
+            max(self.min_x, other.min_x),
+            max(self.min_y, other.min_y),
+            min(self.max_x, other.max_x),
+            min(self.max_y, other.max_y),
+        );
+
+        if r.is_empty() {
+            None
+        } else {
+            Some(r)
+        }
+    }
+
+    // the positions of self that aren't in other, as at most 4 disjoint
+    // rectangles: full height slabs left and right of the overlap, then what
+    // is above and below it
+    pub fn subtract(&self, other: &Rect) -> Vec<Rect> {
+        let i = match self.intersection(other) {
+            Some(i) => i,
+            None => return vec![*self],
+        };
+
+        let pieces = [
+            Rect::new(self.min_x, self.min_y, i.min_x - 1, self.max_y),
+            Rect::new(i.max_x + 1, self.min_y, self.max_x, self.max_y),
+            Rect::new(i.min_x, self.min_y, i.max_x, i.min_y - 1),
+            Rect::new(i.min_x, i.max_y + 1, i.max_x, self.max_y),
+        ];
+
+        pieces.into_iter().filter(|r| !r.is_empty()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let rect = Rect::empty();
+        assert!(rect.is_empty());
+        assert_eq!(0, rect.width());
+        assert_eq!(0, rect.height());
+        assert!(!rect.contains(0, 0));
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut rect = Rect::empty();
+        rect.extend(-3, 5);
+        assert_eq!(Rect::new(-3, 5, -3, 5), rect);
+        assert_eq!(1, rect.width());
+
+        rect.extend(2, 1);
+        assert_eq!(Rect::new(-3, 1, 2, 5), rect);
+        assert_eq!(6, rect.width());
+        assert_eq!(5, rect.height());
+        assert!(rect.contains(2, 5));
+        assert!(!rect.contains(3, 5));
+    }
+
+    #[test]
+    fn test_intersection() {
+        // fabric claims #1 and #2 overlap in 4 square inches, #3 in none
+        let a = Rect::new(1, 3, 4, 6);
+        let b = Rect::new(3, 1, 6, 4);
+        let c = Rect::new(5, 5, 6, 6);
+
+        assert_eq!(Some(Rect::new(3, 3, 4, 4)), a.intersection(&b));
+        assert_eq!(4, a.intersection(&b).unwrap().area());
+        assert!(!a.overlaps(&c));
+        assert_eq!(None, a.intersection(&c));
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = Rect::new(0, 0, 9, 9);
+        let hole = Rect::new(3, 3, 5, 5);
+
+        let pieces = a.subtract(&hole);
+        assert_eq!(4, pieces.len());
+        assert_eq!(100 - 9, pieces.iter().map(|r| r.area()).sum::<i64>());
+        for (i, p) in pieces.iter().enumerate() {
+            assert!(!p.overlaps(&hole));
+            assert!(pieces[i + 1..].iter().all(|q| !p.overlaps(q)));
+        }
+
+        assert_eq!(
+            vec![Rect::new(0, 0, 1, 9)],
+            a.subtract(&Rect::new(2, -5, 12, 15))
+        );
+        assert_eq!(vec![a], a.subtract(&Rect::new(20, 20, 30, 30)));
+        assert!(a.subtract(&a).is_empty());
+    }
+}
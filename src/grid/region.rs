@@ -0,0 +1,267 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::{Grid, Point, Rect};
+
+// every position reachable from start through straight steps onto passable
+// positions, start included, the caller is responsible for keeping it finite
+pub fn flood_fill<P, F>(start: P, passable: F) -> HashSet<Point>
+where
+    P: Into<Point>,
+    F: Fn(Point) -> bool,
+{
+    let start = start.into();
+    let mut visited = HashSet::new();
+    let mut q = VecDeque::new();
+
+    visited.insert(start);
+    q.push_back(start);
+
+    while let Some(cur) = q.pop_front() {
+        for n in cur.neighbours4() {
+            if !visited.contains(&n) && passable(n) {
+                visited.insert(n);
+                q.push_back(n);
+            }
+        }
+    }
+
+    visited
+}
+
+// a straight connected set of cells
+pub struct Region {
+    pub id: usize,
+    pub cells: HashSet<Point>,
+    pub bounds: Rect,
+}
+
+impl Region {
+    fn new(id: usize, cells: HashSet<Point>) -> Region {
+        let mut bounds = Rect::empty();
+        for c in cells.iter() {
+            bounds.extend(c.x, c.y);
+        }
+
+        Region { id, cells, bounds }
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    // number of cell edges facing something outside the region
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|c| c.neighbours4().filter(|n| !self.cells.contains(n)).count())
+            .sum()
+    }
+
+    // number of straight fence sections around the region, holes included,
+    // counted as corners since every side ends in exactly one
+    pub fn sides(&self) -> usize {
+        let mut corners = 0;
+        for c in self.cells.iter() {
+            for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                let horizontal = self.cells.contains(&Point::new(c.x + dx, c.y));
+                let vertical = self.cells.contains(&Point::new(c.x, c.y + dy));
+                let diagonal = self.cells.contains(&Point::new(c.x + dx, c.y + dy));
+
+                // convex corners, and concave ones seen from the inside
+                if (!horizontal && !vertical) || (horizontal && vertical && !diagonal) {
+                    corners += 1;
+                }
+            }
+        }
+
+        corners
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Copy,
+{
+    // connected regions of cells accepted by include, ids are assigned in
+    // reading order of each region's first cell
+    pub fn regions_where<F>(&self, include: F) -> Vec<Region>
+    where
+        F: Fn(T) -> bool,
+    {
+        self.regions_by(|a, b| include(a) && include(b), &include)
+    }
+
+    // connected regions of equal cells, covering the whole grid
+    pub fn regions(&self) -> Vec<Region>
+    where
+        T: PartialEq,
+    {
+        self.regions_by(|a, b| a == b, |_| true)
+    }
+
+    fn regions_by<S, F>(&self, same: S, include: F) -> Vec<Region>
+    where
+        S: Fn(T, T) -> bool,
+        F: Fn(T) -> bool,
+    {
+        let mut positions: Vec<Point> = self
+            .data
+            .iter()
+            .filter(|(_, v)| include(**v))
+            .map(|(p, _)| Point::from(*p))
+            .collect();
+        positions.sort_by_key(|p| (p.y, p.x));
+
+        let mut seen = HashSet::new();
+        let mut regions = vec![];
+        for start in positions {
+            if seen.contains(&start) {
+                continue;
+            }
+
+            let value = self.get_point(start).unwrap();
+            let cells = flood_fill(start, |p| match self.get_point(p) {
+                Some(v) => same(value, v),
+                None => false,
+            });
+            seen.extend(cells.iter().copied());
+            regions.push(Region::new(regions.len(), cells));
+        }
+
+        regions
+    }
+}
+
+impl Grid<char> {
+    // whether p lies inside the loop of pipe glyphs (| - L J 7 F) whose cells
+    // are given, counting how often a ray going left crosses the loop, the
+    // start tile must already be replaced by its pipe
+    pub fn is_enclosed<P>(&self, p: P, path: &HashSet<Point>) -> bool
+    where
+        P: Into<Point>,
+    {
+        let p = p.into();
+        if path.contains(&p) {
+            return false;
+        }
+
+        // only count pipes reaching north, so running along a horizontal
+        // stretch like L--7 counts once and L--J twice
+        let crossings = (self.min_x()..p.x)
+            .map(|x| Point::new(x, p.y))
+            .filter(|c| path.contains(c) && matches!(self.get_point(*c), Some('|' | 'L' | 'J')))
+            .count();
+
+        crossings % 2 == 1
+    }
+
+    // every position within the bounding box enclosed by the loop
+    pub fn enclosed(&self, path: &HashSet<Point>) -> HashSet<Point> {
+        let mut inside = HashSet::new();
+        for y in self.min_y()..=self.max_y() {
+            let mut crossings = 0;
+            for x in self.min_x()..=self.max_x() {
+                let p = Point::new(x, y);
+                if path.contains(&p) {
+                    if matches!(self.get_point(p), Some('|' | 'L' | 'J')) {
+                        crossings += 1;
+                    }
+                } else if crossings % 2 == 1 {
+                    inside.insert(p);
+                }
+            }
+        }
+
+        inside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_grid() -> Grid<char> {
+        Grid::new(&[
+            "AAAA".to_string(),
+            "BBCD".to_string(),
+            "BBCC".to_string(),
+            "EEEC".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let filled = flood_fill((0, 0), |p| p.x.abs() <= 2 && p.y.abs() <= 1);
+        assert_eq!(15, filled.len());
+
+        let filled = flood_fill((0, 0), |_p| false);
+        assert_eq!(1, filled.len());
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = get_grid();
+        let regions = grid.regions();
+
+        assert_eq!(5, regions.len());
+        let metrics: Vec<(usize, usize, usize)> = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)],
+            metrics
+        );
+        assert_eq!(Rect::new(2, 1, 3, 3), regions[2].bounds);
+        assert_eq!(2, regions[2].id);
+        assert!(regions[3].cells.contains(&Point::new(3, 1)));
+    }
+
+    #[test]
+    fn test_sides_with_hole() {
+        let grid = Grid::new(&[
+            "OOOOO".to_string(),
+            "OXOXO".to_string(),
+            "OOOOO".to_string(),
+            "OXOXO".to_string(),
+            "OOOOO".to_string(),
+        ]);
+        let regions = grid.regions_where(|c| c == 'O');
+
+        assert_eq!(1, regions.len());
+        assert_eq!(21, regions[0].area());
+        assert_eq!(36, regions[0].perimeter());
+        assert_eq!(20, regions[0].sides());
+    }
+
+    #[test]
+    fn test_enclosed() {
+        // pipe maze example with the start tile already replaced, 4 enclosed
+        // tiles and a gap squeezed between pipes that doesn't count
+        let grid = Grid::new(&[
+            "..........".to_string(),
+            ".F------7.".to_string(),
+            ".|F----7|.".to_string(),
+            ".||....||.".to_string(),
+            ".||....||.".to_string(),
+            ".|L-7F-J|.".to_string(),
+            ".|..||..|.".to_string(),
+            ".L--JL--J.".to_string(),
+            "..........".to_string(),
+        ]);
+        let path: HashSet<Point> = grid
+            .data
+            .iter()
+            .filter(|(_, c)| **c != '.')
+            .map(|(p, _)| Point::from(*p))
+            .collect();
+
+        let inside = grid.enclosed(&path);
+        assert_eq!(4, inside.len());
+        assert!(inside.contains(&Point::new(2, 6)));
+        assert!(!inside.contains(&Point::new(4, 3)));
+        assert!(grid.is_enclosed((7, 6), &path));
+        assert!(!grid.is_enclosed((5, 4), &path));
+        assert!(!grid.is_enclosed((1, 1), &path));
+    }
+}
@@ -0,0 +1,132 @@
+use super::Grid;
+
+// Transformations map the bounding box onto itself (or its transpose), keeping
+// the top left corner where it was.
+impl<T> Grid<T>
+where
+    T: Copy,
+{
+    // f maps offsets from the top left corner to new offsets
+    fn transform<F>(&self, f: F) -> Grid<T>
+    where
+        F: Fn(i64, i64) -> (i64, i64),
+    {
+        let (min_x, min_y) = (self.min_x(), self.min_y());
+        let mut grid = Grid::empty();
+        for (pos, value) in self.data.iter() {
+            let (dx, dy) = f(pos.0 - min_x, pos.1 - min_y);
+            grid.set(*value, min_x + dx, min_y + dy);
+        }
+
+        grid
+    }
+
+    pub fn rotate_cw(&self) -> Grid<T> {
+        let h = self.height();
+        self.transform(|dx, dy| (h - 1 - dy, dx))
+    }
+
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        let w = self.width();
+        self.transform(|dx, dy| (dy, w - 1 - dx))
+    }
+
+    pub fn rotate_180(&self) -> Grid<T> {
+        let (w, h) = (self.width(), self.height());
+        self.transform(|dx, dy| (w - 1 - dx, h - 1 - dy))
+    }
+
+    // mirror left to right
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let w = self.width();
+        self.transform(|dx, dy| (w - 1 - dx, dy))
+    }
+
+    // mirror top to bottom
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let h = self.height();
+        self.transform(|dx, dy| (dx, h - 1 - dy))
+    }
+
+    // mirror along the diagonal from the top left corner
+    pub fn transpose(&self) -> Grid<T> {
+        self.transform(|dx, dy| (dy, dx))
+    }
+
+    // all 8 rotations and reflections, starting with the grid itself followed
+    // by its clockwise rotations, then the same for the horizontally flipped grid
+    pub fn orientations(&self) -> impl Iterator<Item = Grid<T>> + '_ {
+        (0..8).map(move |i| {
+            let mut grid = if i < 4 {
+                self.clone()
+            } else {
+                self.flip_horizontal()
+            };
+            for _ in 0..i % 4 {
+                grid = grid.rotate_cw();
+            }
+            grid
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_grid() -> Grid<char> {
+        Grid::new(&["ab".to_string(), "cd".to_string(), "ef".to_string()])
+    }
+
+    #[test]
+    fn test_rotate() {
+        let grid = get_grid();
+
+        assert_eq!(vec!["eca", "fdb"], grid.rotate_cw().prepare_print());
+        assert_eq!(vec!["bdf", "ace"], grid.rotate_ccw().prepare_print());
+        assert_eq!(vec!["fe", "dc", "ba"], grid.rotate_180().prepare_print());
+        assert_eq!(grid, grid.rotate_cw().rotate_ccw());
+        assert_eq!(grid.rotate_180(), grid.rotate_cw().rotate_cw());
+    }
+
+    #[test]
+    fn test_flip() {
+        let grid = get_grid();
+
+        assert_eq!(
+            vec!["ba", "dc", "fe"],
+            grid.flip_horizontal().prepare_print()
+        );
+        assert_eq!(vec!["ef", "cd", "ab"], grid.flip_vertical().prepare_print());
+        assert_eq!(vec!["ace", "bdf"], grid.transpose().prepare_print());
+        assert_eq!(grid, grid.transpose().transpose());
+    }
+
+    #[test]
+    fn test_keeps_origin() {
+        let mut grid = Grid::empty();
+        grid.set(1, -3, 5);
+        grid.set(2, -2, 5);
+
+        let rotated = grid.rotate_cw();
+        assert_eq!(Some(1), rotated.get(-3, 5));
+        assert_eq!(Some(2), rotated.get(-3, 6));
+    }
+
+    #[test]
+    fn test_orientations() {
+        let grid = get_grid();
+        let orientations: Vec<Vec<String>> =
+            grid.orientations().map(|g| g.prepare_print()).collect();
+
+        assert_eq!(8, orientations.len());
+        assert_eq!(grid.prepare_print(), orientations[0]);
+        for i in 0..8 {
+            for j in i + 1..8 {
+                assert_ne!(orientations[i], orientations[j]);
+            }
+        }
+        assert!(orientations.contains(&grid.flip_vertical().prepare_print()));
+        assert!(orientations.contains(&grid.transpose().prepare_print()));
+    }
+}
@@ -0,0 +1,238 @@
+use std::fmt::Display;
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+
+use super::{Grid, Point, Rect};
+
+// the eight basic terminal colours
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    // SGR foreground code
+    fn code(&self) -> u8 {
+        30 + *self as u8
+    }
+}
+
+// Shows successive grids in place in the terminal, redrawing from the top left
+// corner for every frame. When stdout isn't a terminal (or plain is asked for)
+// frames are printed one after another as text, without colours or delays.
+pub struct Viz<T> {
+    colors: Vec<(T, Color)>,
+    viewport: Option<(i64, i64)>,
+    delay: Duration,
+    ansi: bool,
+    frames: usize,
+}
+
+impl<T> Viz<T>
+where
+    T: Copy + PartialEq + Display,
+{
+    pub fn new() -> Viz<T> {
+        Viz {
+            colors: vec![],
+            viewport: None,
+            delay: Duration::from_millis(50),
+            ansi: io::stdout().is_terminal(),
+            frames: 0,
+        }
+    }
+
+    // draw cells holding value in color
+    pub fn with_color(mut self, value: T, color: Color) -> Viz<T> {
+        self.colors.push((value, color));
+        self
+    }
+
+    // only show width x height cells, centred on the focus point if given
+    pub fn with_viewport(mut self, width: i64, height: i64) -> Viz<T> {
+        self.viewport = Some((width, height));
+        self
+    }
+
+    // pause after every frame, only when drawing to a terminal
+    pub fn with_delay(mut self, delay: Duration) -> Viz<T> {
+        self.delay = delay;
+        self
+    }
+
+    // print frames as plain text even on a terminal
+    pub fn plain(mut self) -> Viz<T> {
+        self.ansi = false;
+        self
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    // the part of the grid that is shown, the whole bounding box unless a
+    // viewport was set
+    fn visible(&self, grid: &Grid<T>, focus: Option<Point>) -> Rect {
+        let bounds = grid.bounds();
+        match self.viewport {
+            None => bounds,
+            Some((width, height)) => {
+                let (x, y) = match focus {
+                    Some(p) => (p.x - width / 2, p.y - height / 2),
+                    None => (bounds.min_x, bounds.min_y),
+                };
+                Rect::new(x, y, x + width - 1, y + height - 1)
+            }
+        }
+    }
+
+    // the frame as written to the terminal, unset cells shown as '.'
+    pub fn render(&self, grid: &Grid<T>, focus: Option<Point>) -> String {
+        let visible = self.visible(grid, focus);
+        let mut output = String::new();
+
+        if self.ansi {
+            // clear the screen once, then only move the cursor home
+            if self.frames == 0 {
+                output.push_str("\x1b[2J");
+            }
+            output.push_str("\x1b[H");
+        }
+
+        for y in visible.min_y..=visible.max_y {
+            for x in visible.min_x..=visible.max_x {
+                let cell = grid.get(x, y);
+                let color = cell.and_then(|v| {
+                    self.colors
+                        .iter()
+                        .find(|(value, _)| *value == v)
+                        .map(|(_, c)| *c)
+                });
+
+                match (cell, color) {
+                    (Some(v), Some(c)) if self.ansi => {
+                        output.push_str(&format!("\x1b[{}m{}\x1b[0m", c.code(), v))
+                    }
+                    (Some(v), _) => output.push_str(&v.to_string()),
+                    (None, _) => output.push('.'),
+                }
+            }
+            if self.ansi {
+                // wipe whatever a wider previous frame left on this line
+                output.push_str("\x1b[K");
+            }
+            output.push('\n');
+        }
+
+        if self.ansi {
+            output.push_str("\x1b[J");
+        }
+
+        output
+    }
+
+    // draw the next frame to stdout
+    pub fn show(&mut self, grid: &Grid<T>) -> io::Result<()> {
+        self.show_at(grid, None)
+    }
+
+    // draw the next frame to stdout, centring the viewport on focus
+    pub fn show_at(&mut self, grid: &Grid<T>, focus: Option<Point>) -> io::Result<()> {
+        let mut out = io::stdout().lock();
+        self.write_frame(&mut out, grid, focus)
+    }
+
+    fn write_frame<W>(
+        &mut self,
+        out: &mut W,
+        grid: &Grid<T>,
+        focus: Option<Point>,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        if !self.ansi && self.frames > 0 {
+            writeln!(out)?;
+        }
+        write!(out, "{}", self.render(grid, focus))?;
+        out.flush()?;
+        self.frames += 1;
+
+        if self.ansi && !self.delay.is_zero() {
+            thread::sleep(self.delay);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Default for Viz<T>
+where
+    T: Copy + PartialEq + Display,
+{
+    fn default() -> Viz<T> {
+        Viz::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_grid() -> Grid<char> {
+        Grid::new(&["L.LL".to_string(), "#L.#".to_string(), "L#LL".to_string()])
+    }
+
+    #[test]
+    fn test_plain() {
+        let mut viz = Viz::new().with_color('#', Color::Red).plain();
+        assert_eq!("L.LL\n#L.#\nL#LL\n", viz.render(&get_grid(), None));
+
+        let mut out = vec![];
+        viz.write_frame(&mut out, &get_grid(), None).unwrap();
+        viz.write_frame(&mut out, &get_grid(), None).unwrap();
+        assert_eq!(2, viz.frames());
+        assert_eq!(
+            "L.LL\n#L.#\nL#LL\n\nL.LL\n#L.#\nL#LL\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_viewport() {
+        let viz = Viz::new().with_viewport(3, 1).plain();
+        assert_eq!("L.L\n", viz.render(&get_grid(), None));
+        assert_eq!("#LL\n", viz.render(&get_grid(), Some(Point::new(2, 2))));
+        assert_eq!("..L\n", viz.render(&get_grid(), Some(Point::new(-1, 0))));
+    }
+
+    #[test]
+    fn test_ansi() {
+        let mut viz = Viz::new()
+            .with_color('#', Color::Red)
+            .with_viewport(2, 1)
+            .with_delay(Duration::ZERO);
+        viz.ansi = true;
+
+        let grid = get_grid();
+        let mut out = vec![];
+        viz.write_frame(&mut out, &grid, Some(Point::new(1, 1)))
+            .unwrap();
+        viz.write_frame(&mut out, &grid, Some(Point::new(1, 1)))
+            .unwrap();
+
+        let frame = "\x1b[H\x1b[31m#\x1b[0mL\x1b[K\n\x1b[J";
+        assert_eq!(
+            format!("\x1b[2J{}{}", frame, frame),
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use super::Grid;
+
+// A view of a grid that repeats forever along the wrapped axes, tiling the
+// bounding box. Coordinates on an axis that isn't wrapped are used as is.
+pub struct WrappingGrid<'a, T> {
+    grid: &'a Grid<T>,
+    wrap_x: bool,
+    wrap_y: bool,
+}
+
+impl<T> Grid<T> {
+    pub fn wrapping(&self, wrap_x: bool, wrap_y: bool) -> WrappingGrid<'_, T> {
+        WrappingGrid {
+            grid: self,
+            wrap_x,
+            wrap_y,
+        }
+    }
+}
+
+impl<T> WrappingGrid<'_, T> {
+    // position inside the bounding box and the index of the tile it fell in,
+    // tile (0, 0) being the grid itself
+    pub fn locate(&self, x: i64, y: i64) -> ((i64, i64), (i64, i64)) {
+        let bounds = self.grid.bounds();
+        let (x, tile_x) = wrap(x, bounds.min_x, bounds.width(), self.wrap_x);
+        let (y, tile_y) = wrap(y, bounds.min_y, bounds.height(), self.wrap_y);
+
+        ((x, y), (tile_x, tile_y))
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<T>
+    where
+        T: Copy,
+    {
+        let ((x, y), _) = self.locate(x, y);
+        self.grid.get(x, y)
+    }
+
+    // the cell at (x, y) along with the index of the tile it is in
+    pub fn get_tiled(&self, x: i64, y: i64) -> Option<(T, (i64, i64))>
+    where
+        T: Copy,
+    {
+        let ((x, y), tile) = self.locate(x, y);
+        self.grid.get(x, y).map(|v| (v, tile))
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        self.grid
+    }
+}
+
+fn wrap(v: i64, min: i64, size: i64, enabled: bool) -> (i64, i64) {
+    if !enabled || size == 0 {
+        return (v, 0);
+    }
+
+    (min + (v - min).rem_euclid(size), (v - min).div_euclid(size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_grid() -> Grid<char> {
+        Grid::new(&["ab".to_string(), "cd".to_string(), "ef".to_string()])
+    }
+
+    #[test]
+    fn test_wrap_both() {
+        let grid = get_grid();
+        let wrapping = grid.wrapping(true, true);
+
+        assert_eq!(Some('a'), wrapping.get(0, 0));
+        assert_eq!(Some('b'), wrapping.get(3, 3));
+        assert_eq!(Some('f'), wrapping.get(-1, -1));
+        assert_eq!(Some('c'), wrapping.get(-200, 1000));
+    }
+
+    #[test]
+    fn test_wrap_one_axis() {
+        let grid = get_grid();
+        let wrapping = grid.wrapping(true, false);
+
+        assert_eq!(Some('d'), wrapping.get(7, 1));
+        assert_eq!(None, wrapping.get(0, 3));
+        assert_eq!(None, wrapping.get(0, -1));
+    }
+
+    #[test]
+    fn test_tiles() {
+        let grid = get_grid();
+        let wrapping = grid.wrapping(true, true);
+
+        assert_eq!(((1, 2), (0, 0)), wrapping.locate(1, 2));
+        assert_eq!(((1, 2), (-1, -1)), wrapping.locate(-1, -1));
+        assert_eq!(Some(('c', (2, 1))), wrapping.get_tiled(4, 4));
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Grid<char> = Grid::empty();
+        assert_eq!(None, grid.wrapping(true, true).get(3, 3));
+    }
+}
@@ -24,14 +24,14 @@ mod tests {
     #[test]
     fn test_lines_from_file() {
         let lines = lines_from_file("README.md");
-        assert!(lines.len() > 0);
+        assert!(!lines.is_empty());
         assert_eq!(35, lines[0].as_bytes()[0])
     }
 
     #[test]
     fn test_from_file() {
         let f = from_file("README.md");
-        assert!(f.len() > 0);
+        assert!(!f.is_empty());
         assert_eq!(35, f.as_bytes()[0])
     }
 }
@@ -3,6 +3,7 @@ extern crate lazy_static;
 
 pub mod collections;
 mod examples;
+pub mod geometry;
 pub mod graph;
 pub mod grid;
 pub mod io;
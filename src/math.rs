@@ -1,7 +1,58 @@
+use std::ops::{Rem, Sub};
+
 pub fn manhattan_dist(x1: i64, y1: i64, x2: i64, y2: i64) -> i64 {
     (x2 - x1).abs() + (y2 - y1).abs()
 }
 
+// for any signed integer type, always non-negative, gcd(0, 0) is 0
+pub fn gcd<T>(a: T, b: T) -> T
+where
+    T: Copy + Default + PartialOrd + Sub<Output = T> + Rem<Output = T>,
+{
+    let zero = T::default();
+    let abs = |n: T| if n < zero { zero - n } else { n };
+
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != zero {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+// the corners as i128, so that nothing below overflows on i64 coordinates
+fn widen(corners: &[(i64, i64)]) -> impl Iterator<Item = ((i128, i128), (i128, i128))> + '_ {
+    let n = corners.len();
+    (0..n).map(move |i| {
+        let (a, b) = (corners[i], corners[(i + 1) % n]);
+        ((a.0 as i128, a.1 as i128), (b.0 as i128, b.1 as i128))
+    })
+}
+
+// twice the signed area of the polygon, positive when the corners go
+// counterclockwise with y up (clockwise on screen)
+fn twice_area(corners: &[(i64, i64)]) -> i128 {
+    widen(corners).map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum()
+}
+
+// area of the simple polygon through the corners in order, rounded down when
+// it isn't whole, which can only happen with diagonal edges
+// https://en.wikipedia.org/wiki/Shoelace_formula
+pub fn shoelace_area(corners: &[(i64, i64)]) -> i128 {
+    twice_area(corners).abs() / 2
+}
+
+// lattice points on the edges of the polygon, corners included
+pub fn boundary_points(corners: &[(i64, i64)]) -> i128 {
+    widen(corners).map(|(a, b)| gcd(b.0 - a.0, b.1 - a.1)).sum()
+}
+
+// lattice points strictly inside the polygon, by Pick's theorem
+// https://en.wikipedia.org/wiki/Pick%27s_theorem
+pub fn interior_points(corners: &[(i64, i64)]) -> i128 {
+    (twice_area(corners).abs() - boundary_points(corners) + 2) / 2
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,4 +63,38 @@ mod tests {
         assert_eq!(20, manhattan_dist(0, 0, 10, 10));
         assert_eq!(20, manhattan_dist(-5, -5, 5, 5));
     }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(6, gcd(-12, 18));
+        assert_eq!(5, gcd(0, -5));
+        assert_eq!(0, gcd(0, 0));
+        assert_eq!(3_i128 << 70, gcd(9_i128 << 70, -(6_i128 << 70)));
+    }
+
+    #[test]
+    fn test_pick() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(16, shoelace_area(&square));
+        assert_eq!(16, boundary_points(&square));
+        assert_eq!(9, interior_points(&square));
+
+        // the corners may go either way round
+        let triangle = [(0, 0), (0, 3), (3, 0)];
+        assert_eq!(4, shoelace_area(&triangle));
+        assert_eq!(9, boundary_points(&triangle));
+        assert_eq!(1, interior_points(&triangle));
+
+        // dig plan sized coordinates don't overflow
+        let big = 1 << 40;
+        let huge = [(0, 0), (big, 0), (big, big), (0, big)];
+        assert_eq!((big as i128) * (big as i128), shoelace_area(&huge));
+        assert_eq!((big as i128 - 1).pow(2), interior_points(&huge));
+
+        // edges longer than i64 can hold
+        let wide = [(i64::MIN, 0), (i64::MAX, 0), (i64::MAX, 1), (i64::MIN, 1)];
+        assert_eq!(2 * (u64::MAX as i128) + 2, boundary_points(&wide));
+        assert_eq!(u64::MAX as i128, shoelace_area(&wide));
+    }
 }